@@ -0,0 +1,142 @@
+use std::future::Future;
+
+use futures_util::{stream, StreamExt};
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::payload::BatchItemResult;
+use crate::{
+    delete_container_inner, kill_container_inner, pause_container_inner, remove_image_inner,
+    remove_network_inner, remove_volume_inner, start_container_inner, stop_container_inner,
+    AppState,
+};
+
+/// Upper bound on how many requests a batch keeps in flight against the daemon.
+const BATCH_CONCURRENCY: usize = 4;
+
+/// Runs `op` for every name with bounded concurrency and collects one result
+/// per item, in input order. A failing item never aborts the rest of the batch.
+async fn run_batch<F, Fut>(names: Vec<String>, op: F) -> Vec<BatchItemResult>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<(), CommandError>>,
+{
+    stream::iter(names)
+        .map(|name| {
+            let fut = op(name.clone());
+            async move {
+                match fut.await {
+                    Ok(()) => BatchItemResult {
+                        name,
+                        success: true,
+                        error: None,
+                    },
+                    Err(e) => BatchItemResult {
+                        name,
+                        success: false,
+                        error: Some(e),
+                    },
+                }
+            }
+        })
+        .buffered(BATCH_CONCURRENCY)
+        .collect()
+        .await
+}
+
+#[tauri::command]
+pub async fn start_containers(
+    state: State<'_, AppState>,
+    container_names: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    Ok(run_batch(container_names, |name| async move {
+        start_container_inner(docker, &name).await
+    })
+    .await)
+}
+
+#[tauri::command]
+pub async fn stop_containers(
+    state: State<'_, AppState>,
+    container_names: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    let timeout = state.settings.get().stop_timeout_seconds;
+    Ok(run_batch(container_names, |name| async move {
+        stop_container_inner(docker, &name, timeout).await
+    })
+    .await)
+}
+
+#[tauri::command]
+pub async fn kill_containers(
+    state: State<'_, AppState>,
+    container_names: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    Ok(run_batch(container_names, |name| async move {
+        kill_container_inner(docker, &name).await
+    })
+    .await)
+}
+
+#[tauri::command]
+pub async fn delete_containers(
+    state: State<'_, AppState>,
+    container_names: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    Ok(run_batch(container_names, |name| async move {
+        delete_container_inner(docker, &name).await
+    })
+    .await)
+}
+
+#[tauri::command]
+pub async fn pause_containers(
+    state: State<'_, AppState>,
+    container_names: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    Ok(run_batch(container_names, |name| async move {
+        pause_container_inner(docker, &name).await
+    })
+    .await)
+}
+
+#[tauri::command]
+pub async fn remove_images(
+    state: State<'_, AppState>,
+    images: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    Ok(run_batch(images, |image| async move {
+        remove_image_inner(docker, &image).await
+    })
+    .await)
+}
+
+#[tauri::command]
+pub async fn remove_volumes(
+    state: State<'_, AppState>,
+    volume_names: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    Ok(run_batch(volume_names, |name| async move {
+        remove_volume_inner(docker, &name).await
+    })
+    .await)
+}
+
+#[tauri::command]
+pub async fn remove_networks(
+    state: State<'_, AppState>,
+    network_ids: Vec<String>,
+) -> Result<Vec<BatchItemResult>, CommandError> {
+    let docker = &state.docker;
+    Ok(run_batch(network_ids, |id| async move {
+        remove_network_inner(docker, &id).await
+    })
+    .await)
+}
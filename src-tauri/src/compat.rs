@@ -0,0 +1,300 @@
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use bollard::system::Version;
+use bollard::Docker;
+use bollard::API_DEFAULT_VERSION;
+use tauri::State;
+
+use crate::engine::Endpoint;
+use crate::error::CommandError;
+use crate::payload::{EngineKind, EngineProfile, Feature, FeatureSupport};
+use crate::AppState;
+
+#[cfg(unix)]
+const DEFAULT_SOCKET: &str = "/var/run/docker.sock";
+const CONNECT_TIMEOUT: u64 = 120;
+#[cfg(windows)]
+const DEFAULT_NAMED_PIPE: &str = r"\\.\pipe\docker_engine";
+const FEATURES: [Feature; 2] = [Feature::Swarm, Feature::NetworkLinks];
+
+/// Picks the endpoint to talk to: `host` (the configured Docker host or
+/// `DOCKER_HOST`) when it names a unix socket, named pipe or tcp address,
+/// otherwise the first well-known Docker-compatible socket that exists (the
+/// default named pipe on Windows).
+pub fn resolve_endpoint(host: Option<&str>) -> Endpoint {
+    if let Some(endpoint) = host.and_then(Endpoint::parse) {
+        return endpoint;
+    }
+
+    default_endpoint()
+}
+
+#[cfg(unix)]
+fn default_endpoint() -> Endpoint {
+    let path = socket_candidates()
+        .into_iter()
+        .find(|path| path.exists())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_SOCKET));
+    Endpoint::Unix(path)
+}
+
+#[cfg(windows)]
+fn default_endpoint() -> Endpoint {
+    Endpoint::NamedPipe(DEFAULT_NAMED_PIPE.to_string())
+}
+
+/// Connects bollard to `endpoint`. Unix sockets include the local end of an
+/// `SshTunnel`. TLS hosts (and transports this platform lacks) are refused
+/// rather than handed to bollard's defaults, which would quietly connect
+/// somewhere else.
+pub fn connect(endpoint: &Endpoint) -> Result<Docker, CommandError> {
+    let docker = match endpoint {
+        #[cfg(unix)]
+        Endpoint::Unix(path) => Docker::connect_with_unix(
+            &path.to_string_lossy(),
+            CONNECT_TIMEOUT,
+            API_DEFAULT_VERSION,
+        ),
+        #[cfg(windows)]
+        Endpoint::NamedPipe(_) => Docker::connect_with_named_pipe(
+            &endpoint.to_string(),
+            CONNECT_TIMEOUT,
+            API_DEFAULT_VERSION,
+        ),
+        Endpoint::Tcp {
+            address,
+            tls: false,
+        } => Docker::connect_with_http(address, CONNECT_TIMEOUT, API_DEFAULT_VERSION),
+        _ => {
+            return Err(CommandError::Unsupported(format!(
+                "Docker host '{}' is not supported",
+                endpoint
+            )))
+        }
+    };
+
+    docker.map_err(|e| {
+        CommandError::DockerError(format!("Failed to connect to '{}': {}", endpoint, e))
+    })
+}
+
+#[cfg(unix)]
+fn socket_candidates() -> Vec<PathBuf> {
+    let home = std::env::var("HOME").ok().map(PathBuf::from);
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| {
+            let uid = std::fs::metadata(home.as_ref()?).ok()?.uid();
+            Some(PathBuf::from(format!("/run/user/{}", uid)))
+        });
+
+    let mut candidates = vec![PathBuf::from(DEFAULT_SOCKET)];
+    if let Some(runtime_dir) = &runtime_dir {
+        candidates.push(runtime_dir.join("docker.sock"));
+        candidates.push(runtime_dir.join("podman/podman.sock"));
+    }
+    candidates.push(PathBuf::from("/run/docker.sock"));
+    candidates.push(PathBuf::from("/run/podman/podman.sock"));
+    if let Some(home) = &home {
+        candidates.push(home.join(".docker/run/docker.sock"));
+        candidates.push(home.join(".colima/default/docker.sock"));
+        candidates.push(home.join(".colima/docker.sock"));
+        candidates.push(home.join(".rd/docker.sock"));
+        candidates.push(home.join(".orbstack/run/docker.sock"));
+    }
+
+    candidates
+}
+
+fn detect_kind(endpoint: &str, version: &Version) -> EngineKind {
+    let is_podman = version
+        .components
+        .iter()
+        .flatten()
+        .any(|component| component.name.to_lowercase().contains("podman"));
+    let platform = version
+        .platform
+        .as_ref()
+        .map(|platform| platform.name.to_lowercase())
+        .unwrap_or_default();
+    let path = endpoint;
+
+    if is_podman || path.contains("podman") {
+        EngineKind::Podman
+    } else if path.contains("/.orbstack/") || platform.contains("orbstack") {
+        EngineKind::OrbStack
+    } else if path.contains("/.colima/") {
+        EngineKind::Colima
+    } else if path.contains("/.rd/") || platform.contains("rancher") {
+        EngineKind::RancherDesktop
+    } else {
+        EngineKind::Docker
+    }
+}
+
+fn supports(kind: EngineKind, feature: Feature) -> bool {
+    match (kind, feature) {
+        // Podman's compat API has no swarm mode and netavark ignores links.
+        (EngineKind::Podman, Feature::Swarm | Feature::NetworkLinks) => false,
+        _ => true,
+    }
+}
+
+fn engine_label(kind: EngineKind) -> &'static str {
+    match kind {
+        EngineKind::Docker => "Docker",
+        EngineKind::Podman => "Podman",
+        EngineKind::Colima => "Colima",
+        EngineKind::RancherDesktop => "Rancher Desktop",
+        EngineKind::OrbStack => "OrbStack",
+    }
+}
+
+fn feature_label(feature: Feature) -> &'static str {
+    match feature {
+        Feature::Swarm => "Swarm mode",
+        Feature::NetworkLinks => "Legacy container links",
+    }
+}
+
+/// Detects the engine on first use and caches the result for the session.
+pub(crate) async fn engine_profile_inner(state: &AppState) -> Result<&EngineProfile, CommandError> {
+    state
+        .profile
+        .get_or_try_init(|| async {
+            let version = state.docker.version().await.map_err(|e| {
+                CommandError::DockerError(format!("Failed to read engine version: {}", e))
+            })?;
+            let endpoint = state.engine.endpoint().to_string();
+            let kind = detect_kind(&endpoint, &version);
+
+            Ok(EngineProfile {
+                kind,
+                socket_path: endpoint,
+                version: version.version,
+                api_version: version.api_version,
+                features: FEATURES
+                    .into_iter()
+                    .map(|feature| FeatureSupport {
+                        feature,
+                        supported: supports(kind, feature),
+                    })
+                    .collect(),
+            })
+        })
+        .await
+}
+
+pub(crate) async fn require_feature(
+    state: &AppState,
+    feature: Feature,
+) -> Result<(), CommandError> {
+    let kind = engine_profile_inner(state).await?.kind;
+
+    if supports(kind, feature) {
+        Ok(())
+    } else {
+        Err(CommandError::Unsupported(format!(
+            "{} is not available on {}",
+            feature_label(feature),
+            engine_label(kind)
+        )))
+    }
+}
+
+#[tauri::command]
+pub async fn engine_profile(state: State<'_, AppState>) -> Result<EngineProfile, CommandError> {
+    engine_profile_inner(&state).await.cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::models::SystemVersionPlatform;
+    use bollard::system::VersionComponents;
+
+    use super::*;
+
+    fn version(platform: &str, components: &[&str]) -> Version {
+        Version {
+            platform: Some(SystemVersionPlatform {
+                name: platform.to_string(),
+            }),
+            components: Some(
+                components
+                    .iter()
+                    .map(|name| VersionComponents {
+                        name: name.to_string(),
+                        version: "1.0".to_string(),
+                        details: None,
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn detects_engine_kind() {
+        let docker = version("Docker Engine - Community", &["Engine"]);
+        assert_eq!(
+            detect_kind("unix:///var/run/docker.sock", &docker),
+            EngineKind::Docker
+        );
+        assert_eq!(
+            detect_kind(
+                "unix:///var/run/docker.sock",
+                &version("", &["Podman Engine"])
+            ),
+            EngineKind::Podman
+        );
+        assert_eq!(
+            detect_kind("unix:///run/user/1000/podman/podman.sock", &docker),
+            EngineKind::Podman
+        );
+        assert_eq!(
+            detect_kind("unix:///Users/me/.orbstack/run/docker.sock", &docker),
+            EngineKind::OrbStack
+        );
+        assert_eq!(
+            detect_kind("unix:///Users/me/.colima/default/docker.sock", &docker),
+            EngineKind::Colima
+        );
+        assert_eq!(
+            detect_kind("unix:///Users/me/.rd/docker.sock", &docker),
+            EngineKind::RancherDesktop
+        );
+    }
+
+    #[test]
+    fn resolves_configured_host() {
+        assert_eq!(
+            resolve_endpoint(Some("unix:///tmp/docker.sock")),
+            Endpoint::Unix(std::path::PathBuf::from("/tmp/docker.sock"))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_default_endpoint() {
+        assert_eq!(
+            resolve_endpoint(Some("ssh://user@host")),
+            default_endpoint()
+        );
+        assert_eq!(resolve_endpoint(None), default_endpoint());
+    }
+
+    #[test]
+    fn refuses_tls_endpoints() {
+        let endpoint = Endpoint::Tcp {
+            address: "10.0.0.5:2376".to_string(),
+            tls: true,
+        };
+        assert!(matches!(
+            connect(&endpoint),
+            Err(CommandError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,489 @@
+use std::path::{Path, PathBuf};
+
+use bollard::container::{DownloadFromContainerOptions, InspectContainerOptions};
+use bollard::Docker;
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt};
+use tauri::ipc::Channel;
+use tauri::{Emitter, State};
+use tempfile::NamedTempFile;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::engine::{encode, read_body, EngineClient};
+use crate::error::CommandError;
+use crate::payload::{CommittedImage, CopyProgress};
+use crate::{list_images_inner, AppState};
+
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Copies `container_path` (a file or a directory) out of the container into
+/// the host directory `host_path`, the same way `docker cp` does.
+#[tauri::command]
+pub async fn copy_from_container(
+    state: State<'_, AppState>,
+    container_name: &str,
+    container_path: &str,
+    host_path: String,
+    overwrite: Option<bool>,
+    on_progress: Channel<CopyProgress>,
+    operation_id: Option<String>,
+) -> Result<(), CommandError> {
+    state
+        .operations
+        .run(
+            operation_id,
+            "copy-from-container",
+            container_name,
+            copy_from_container_inner(
+                &state.docker,
+                container_name,
+                container_path,
+                host_path,
+                overwrite,
+                on_progress,
+            ),
+        )
+        .await
+}
+
+async fn copy_from_container_inner(
+    docker: &Docker,
+    container_name: &str,
+    container_path: &str,
+    host_path: String,
+    overwrite: Option<bool>,
+    on_progress: Channel<CopyProgress>,
+) -> Result<(), CommandError> {
+    let destination = PathBuf::from(host_path);
+
+    if !destination.is_dir() {
+        return Err(CommandError::UnexpectedError(format!(
+            "Destination '{}' is not a directory",
+            destination.display()
+        )));
+    }
+
+    let target = destination.join(entry_name(Path::new(container_path))?);
+    if target.exists() && !overwrite.unwrap_or(false) {
+        return Err(CommandError::UnexpectedError(format!(
+            "'{}' already exists on the host",
+            target.display()
+        )));
+    }
+
+    let archive = NamedTempFile::new().map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to create temporary archive: {}", e))
+    })?;
+    let mut file = tokio::fs::File::from_std(archive.reopen().map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to open temporary archive: {}", e))
+    })?);
+
+    let mut download = docker.download_from_container(
+        container_name,
+        Some(DownloadFromContainerOptions {
+            path: container_path,
+        }),
+    );
+    let mut bytes_transferred = 0u64;
+
+    while let Some(chunk) = download.next().await {
+        let chunk = chunk.map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to copy '{}' from container '{}': {}",
+                container_path, container_name, e
+            ))
+        })?;
+
+        file.write_all(&chunk).await.map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to write archive: {}", e))
+        })?;
+        bytes_transferred += chunk.len() as u64;
+
+        on_progress
+            .send(CopyProgress {
+                bytes_transferred,
+                total_bytes: None,
+            })
+            .map_err(|e| {
+                CommandError::UnexpectedError(format!("Failed to send copy progress: {}", e))
+            })?;
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to write archive: {}", e)))?;
+    drop(file);
+
+    tokio::task::spawn_blocking(move || {
+        let mut unpacker = tar::Archive::new(archive.reopen()?);
+        unpacker.set_overwrite(true);
+        unpacker.unpack(&destination)
+    })
+    .await
+    .map_err(|e| CommandError::UnexpectedError(format!("Failed to unpack archive: {}", e)))?
+    .map_err(|e| CommandError::UnexpectedError(format!("Failed to unpack archive: {}", e)))?;
+
+    Ok(())
+}
+
+/// Copies the host file or directory `host_path` into the container directory
+/// `container_path`.
+#[tauri::command]
+pub async fn copy_to_container(
+    state: State<'_, AppState>,
+    container_name: &str,
+    host_path: String,
+    container_path: &str,
+    overwrite: Option<bool>,
+    on_progress: Channel<CopyProgress>,
+    operation_id: Option<String>,
+) -> Result<(), CommandError> {
+    state
+        .operations
+        .run(
+            operation_id,
+            "copy-to-container",
+            container_name,
+            copy_to_container_inner(
+                &state.docker,
+                &state.engine,
+                container_name,
+                host_path,
+                container_path,
+                overwrite,
+                on_progress,
+            ),
+        )
+        .await
+}
+
+async fn copy_to_container_inner(
+    docker: &Docker,
+    engine: &EngineClient,
+    container_name: &str,
+    host_path: String,
+    container_path: &str,
+    overwrite: Option<bool>,
+    on_progress: Channel<CopyProgress>,
+) -> Result<(), CommandError> {
+    let overwrite = overwrite.unwrap_or(false);
+    let source = PathBuf::from(host_path);
+
+    let metadata = tokio::fs::metadata(&source).await.map_err(|e| {
+        CommandError::UnexpectedError(format!(
+            "Failed to read '{}' on the host: {}",
+            source.display(),
+            e
+        ))
+    })?;
+    let name = entry_name(&source)?;
+
+    if !overwrite {
+        let target = format!("{}/{}", container_path.trim_end_matches('/'), name);
+        if container_path_exists(docker, container_name, &target).await? {
+            return Err(CommandError::UnexpectedError(format!(
+                "'{}' already exists in container '{}'",
+                target, container_name
+            )));
+        }
+    }
+
+    let archive = tokio::task::spawn_blocking(move || -> std::io::Result<NamedTempFile> {
+        let mut builder = tar::Builder::new(NamedTempFile::new()?);
+        if metadata.is_dir() {
+            builder.append_dir_all(&name, &source)?;
+        } else {
+            builder.append_path_with_name(&source, &name)?;
+        }
+        builder.into_inner()
+    })
+    .await
+    .map_err(|e| CommandError::UnexpectedError(format!("Failed to pack archive: {}", e)))?
+    .map_err(|e| CommandError::UnexpectedError(format!("Failed to pack archive: {}", e)))?;
+
+    let file = tokio::fs::File::open(archive.path())
+        .await
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to open archive: {}", e)))?;
+    let total_bytes = file
+        .metadata()
+        .await
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to open archive: {}", e)))?
+        .len();
+
+    // bollard's upload stream has no way to carry a read error, which would
+    // then look like the end of the archive; the engine client aborts instead.
+    let mut path = format!(
+        "/containers/{}/archive?path={}",
+        encode(container_name),
+        encode(container_path)
+    );
+    if !overwrite {
+        path.push_str("&noOverwriteDirNonDir=true");
+    }
+    let response = engine
+        .put_streaming(
+            &path,
+            "application/x-tar",
+            file_chunks(file, total_bytes, on_progress),
+        )
+        .await?;
+    read_body(response).await?;
+
+    Ok(())
+}
+
+/// Reads `file` in `UPLOAD_CHUNK_SIZE` chunks and reports the running byte
+/// count to `on_progress`. A read or progress error ends the stream with
+/// that error.
+fn file_chunks(
+    file: tokio::fs::File,
+    total_bytes: u64,
+    on_progress: Channel<CopyProgress>,
+) -> impl Stream<Item = std::io::Result<Bytes>> {
+    stream::try_unfold((file, 0u64), move |(mut file, bytes_transferred)| {
+        let on_progress = on_progress.clone();
+        async move {
+            let mut buf = vec![0u8; UPLOAD_CHUNK_SIZE];
+            let read = file.read(&mut buf).await?;
+            if read == 0 {
+                return Ok(None);
+            }
+            buf.truncate(read);
+
+            let bytes_transferred = bytes_transferred + read as u64;
+            on_progress
+                .send(CopyProgress {
+                    bytes_transferred,
+                    total_bytes: Some(total_bytes),
+                })
+                .map_err(|e| {
+                    std::io::Error::other(format!("Failed to send copy progress: {}", e))
+                })?;
+
+            Ok(Some((Bytes::from(buf), (file, bytes_transferred))))
+        }
+    })
+}
+
+fn entry_name(path: &Path) -> Result<String, CommandError> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| {
+            CommandError::UnexpectedError(format!(
+                "'{}' does not name a file or directory",
+                path.display()
+            ))
+        })
+}
+
+async fn container_path_exists(
+    docker: &Docker,
+    container_name: &str,
+    path: &str,
+) -> Result<bool, CommandError> {
+    let mut probe =
+        docker.download_from_container(container_name, Some(DownloadFromContainerOptions { path }));
+
+    match probe.next().await {
+        Some(Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        })) => Ok(false),
+        Some(Err(e)) => Err(CommandError::DockerError(format!(
+            "Failed to inspect '{}' in container '{}': {}",
+            path, container_name, e
+        ))),
+        _ => Ok(true),
+    }
+}
+
+/// Streams the flattened filesystem of a container into a tarball on the host.
+#[tauri::command]
+pub async fn export_container(
+    state: State<'_, AppState>,
+    container_name: &str,
+    host_path: String,
+    overwrite: Option<bool>,
+    on_progress: Channel<CopyProgress>,
+    operation_id: Option<String>,
+) -> Result<(), CommandError> {
+    state
+        .operations
+        .run(
+            operation_id,
+            "export-container",
+            container_name,
+            export_container_inner(
+                &state.docker,
+                container_name,
+                host_path,
+                overwrite,
+                on_progress,
+            ),
+        )
+        .await
+}
+
+async fn export_container_inner(
+    docker: &Docker,
+    container_name: &str,
+    host_path: String,
+    overwrite: Option<bool>,
+    on_progress: Channel<CopyProgress>,
+) -> Result<(), CommandError> {
+    let destination = PathBuf::from(host_path);
+
+    if destination.exists() && !overwrite.unwrap_or(false) {
+        return Err(CommandError::UnexpectedError(format!(
+            "'{}' already exists on the host",
+            destination.display()
+        )));
+    }
+
+    let total_bytes = docker
+        .inspect_container(container_name, Some(InspectContainerOptions { size: true }))
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to inspect container '{}': {}",
+                container_name, e
+            ))
+        })?
+        .size_root_fs
+        .and_then(|size| u64::try_from(size).ok());
+
+    // Write next to the destination and rename on success, so a failed or
+    // cancelled export never leaves a truncated tarball behind.
+    let create_error = |e: std::io::Error| {
+        CommandError::UnexpectedError(format!(
+            "Failed to create '{}': {}",
+            destination.display(),
+            e
+        ))
+    };
+    let dir = match destination.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let (file, temp_path) = NamedTempFile::new_in(dir)
+        .map_err(create_error)?
+        .into_parts();
+    let mut file = tokio::fs::File::from_std(file);
+
+    let mut export = docker.export_container(container_name);
+    let mut bytes_transferred = 0u64;
+
+    while let Some(chunk) = export.next().await {
+        let chunk = chunk.map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to export container '{}': {}",
+                container_name, e
+            ))
+        })?;
+        file.write_all(&chunk).await.map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to write archive: {}", e))
+        })?;
+        bytes_transferred += chunk.len() as u64;
+
+        on_progress
+            .send(CopyProgress {
+                bytes_transferred,
+                total_bytes,
+            })
+            .map_err(|e| {
+                CommandError::UnexpectedError(format!("Failed to send export progress: {}", e))
+            })?;
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to write archive: {}", e)))?;
+    drop(file);
+
+    temp_path.persist(&destination).map_err(|e| {
+        CommandError::UnexpectedError(format!(
+            "Failed to write '{}': {}",
+            destination.display(),
+            e.error
+        ))
+    })?;
+
+    Ok(())
+}
+
+/// Turns a rootfs tarball on the host into a single-layer image tagged `repo:tag`.
+#[tauri::command]
+pub async fn import_image(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    host_path: String,
+    repo: String,
+    tag: Option<String>,
+    on_progress: Channel<CopyProgress>,
+) -> Result<CommittedImage, CommandError> {
+    let docker = &state.docker;
+
+    if repo.trim().is_empty() {
+        return Err(CommandError::UnexpectedError(
+            "A repository name is required to import an image".to_string(),
+        ));
+    }
+    let tag = tag
+        .filter(|tag| !tag.trim().is_empty())
+        .unwrap_or_else(|| "latest".to_string());
+
+    let file = tokio::fs::File::open(&host_path).await.map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to read '{}': {}", host_path, e))
+    })?;
+    let total_bytes = file
+        .metadata()
+        .await
+        .map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to read '{}': {}", host_path, e))
+        })?
+        .len();
+
+    // bollard buffers the whole request body, so the tarball is streamed
+    // through the engine client instead.
+    let path = format!(
+        "/images/create?fromSrc=-&repo={}&tag={}",
+        encode(&repo),
+        encode(&tag)
+    );
+    let response = state
+        .engine
+        .post_streaming(
+            &path,
+            "application/x-tar",
+            file_chunks(file, total_bytes, on_progress),
+        )
+        .await?;
+    let messages = read_body(response).await?;
+
+    // Failures after the upload starts arrive as `error` messages in a 200 response.
+    for message in messages.split(|byte| *byte == b'\n') {
+        let error = serde_json::from_slice::<serde_json::Value>(message)
+            .ok()
+            .and_then(|message| message.get("error")?.as_str().map(str::to_string));
+        if let Some(error) = error {
+            return Err(CommandError::DockerError(format!(
+                "Failed to import image: {}",
+                error
+            )));
+        }
+    }
+
+    let repo_tag = format!("{}:{}", repo, tag);
+    let image = docker.inspect_image(&repo_tag).await.map_err(|e| {
+        CommandError::DockerError(format!("Failed to inspect imported image: {}", e))
+    })?;
+
+    let images = list_images_inner(docker).await?;
+    app_handle.emit("images-changed", images).map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to send image list update: {}", e))
+    })?;
+
+    Ok(CommittedImage {
+        id: image.id.unwrap_or_default(),
+        repo_tag,
+    })
+}
@@ -0,0 +1,354 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::client::conn::http1::SendRequest;
+use hyper::{Method, Request};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+
+use crate::error::CommandError;
+
+type RequestBody = UnsyncBoxBody<Bytes, std::io::Error>;
+
+/// Where the engine listens: a unix socket, a Windows named pipe or a TCP
+/// address (plain HTTP or TLS).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    Unix(PathBuf),
+    NamedPipe(String),
+    Tcp { address: String, tls: bool },
+}
+
+impl Endpoint {
+    /// Parses a `DOCKER_HOST` value. `ssh://` and unknown schemes yield
+    /// `None`; those are reached through a local socket instead.
+    pub fn parse(host: &str) -> Option<Endpoint> {
+        let tls_verify = std::env::var("DOCKER_TLS_VERIFY").is_ok_and(|value| !value.is_empty());
+        Self::parse_with_tls(host, tls_verify)
+    }
+
+    fn parse_with_tls(host: &str, tls_verify: bool) -> Option<Endpoint> {
+        let (scheme, rest) = host.split_once("://")?;
+        match scheme {
+            "unix" => Some(Endpoint::Unix(PathBuf::from(rest))),
+            "npipe" => Some(Endpoint::NamedPipe(rest.replace('/', "\\"))),
+            "tcp" | "http" | "https" => Some(Endpoint::Tcp {
+                address: rest.trim_end_matches('/').to_string(),
+                tls: scheme == "https" || tls_verify,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Endpoint::Unix(path) => write!(f, "unix://{}", path.display()),
+            Endpoint::NamedPipe(name) => write!(f, "npipe://{}", name.replace('\\', "/")),
+            Endpoint::Tcp {
+                address,
+                tls: false,
+            } => write!(f, "tcp://{}", address),
+            Endpoint::Tcp { address, tls: true } => write!(f, "https://{}", address),
+        }
+    }
+}
+
+/// Bare-bones Engine API client for the endpoints bollard 0.18 does not wrap
+/// (swarm membership, tasks, nodes, service logs, configs). It talks to the
+/// same endpoint as the bollard client and reuses bollard's models for
+/// payloads.
+#[derive(Debug, Clone)]
+pub struct EngineClient {
+    endpoint: Endpoint,
+}
+
+impl EngineClient {
+    pub fn new(endpoint: Endpoint) -> Self {
+        EngineClient { endpoint }
+    }
+
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, CommandError> {
+        let body = self.send(Method::GET, path, None).await?;
+        parse_json(body).await
+    }
+
+    pub async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, CommandError> {
+        let body = Full::new(Bytes::from(to_json(body)?))
+            .map_err(|never| match never {})
+            .boxed_unsync();
+        let body = self
+            .send(Method::POST, path, Some(("application/json", body)))
+            .await?;
+        parse_json(body).await
+    }
+
+    pub async fn post_empty(&self, path: &str) -> Result<(), CommandError> {
+        let body = self.send(Method::POST, path, None).await?;
+        read_body(body).await.map(|_| ())
+    }
+
+    pub async fn delete(&self, path: &str) -> Result<(), CommandError> {
+        let body = self.send(Method::DELETE, path, None).await?;
+        read_body(body).await.map(|_| ())
+    }
+
+    /// Returns the raw response body so callers can consume it incrementally.
+    pub async fn stream(&self, path: &str) -> Result<Incoming, CommandError> {
+        self.send(Method::GET, path, None).await
+    }
+
+    /// POST counterpart of `stream`: resolves as soon as the response headers
+    /// arrive, which for `/containers/{id}/wait` means the wait is in place.
+    pub async fn post_stream(&self, path: &str) -> Result<Incoming, CommandError> {
+        self.send(Method::POST, path, None).await
+    }
+
+    /// Streams `body` as the request payload without buffering it, returning
+    /// the raw response body. A read error in `body` aborts the request.
+    pub async fn post_streaming<S>(
+        &self,
+        path: &str,
+        content_type: &str,
+        body: S,
+    ) -> Result<Incoming, CommandError>
+    where
+        S: Stream<Item = std::io::Result<Bytes>> + Send + 'static,
+    {
+        self.send_streaming(Method::POST, path, content_type, body)
+            .await
+    }
+
+    pub async fn put_streaming<S>(
+        &self,
+        path: &str,
+        content_type: &str,
+        body: S,
+    ) -> Result<Incoming, CommandError>
+    where
+        S: Stream<Item = std::io::Result<Bytes>> + Send + 'static,
+    {
+        self.send_streaming(Method::PUT, path, content_type, body)
+            .await
+    }
+
+    async fn send_streaming<S>(
+        &self,
+        method: Method,
+        path: &str,
+        content_type: &str,
+        body: S,
+    ) -> Result<Incoming, CommandError>
+    where
+        S: Stream<Item = std::io::Result<Bytes>> + Send + 'static,
+    {
+        let body = StreamBody::new(body.map_ok(Frame::data)).boxed_unsync();
+        self.send(method, path, Some((content_type, body))).await
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<(&str, RequestBody)>,
+    ) -> Result<Incoming, CommandError> {
+        let mut sender = self.connect().await?;
+
+        let mut request = Request::builder()
+            .method(method)
+            .uri(path)
+            .header("Host", "docker");
+        let body = match body {
+            Some((content_type, body)) => {
+                request = request.header("Content-Type", content_type);
+                body
+            }
+            None => Full::default()
+                .map_err(|never| match never {})
+                .boxed_unsync(),
+        };
+        let request = request
+            .body(body)
+            .map_err(|e| CommandError::UnexpectedError(format!("Invalid request: {}", e)))?;
+
+        let response = sender.send_request(request).await.map_err(|e| {
+            // Errors from a streamed request body only show up as the source.
+            let message = match std::error::Error::source(&e) {
+                Some(source) => format!("{}: {}", e, source),
+                None => e.to_string(),
+            };
+            CommandError::DockerError(format!("Request to '{}' failed: {}", path, message))
+        })?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.into_body());
+        }
+
+        let body = read_body(response.into_body()).await.unwrap_or_default();
+        let message = serde_json::from_slice::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|value| value.get("message")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| String::from_utf8_lossy(&body).trim().to_string());
+
+        Err(CommandError::DockerError(format!(
+            "{} (status {})",
+            message,
+            status.as_u16()
+        )))
+    }
+
+    async fn connect(&self) -> Result<SendRequest<RequestBody>, CommandError> {
+        let connect_error = |e: std::io::Error| {
+            CommandError::DockerError(format!("Failed to connect to '{}': {}", self.endpoint, e))
+        };
+
+        match &self.endpoint {
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                handshake(
+                    tokio::net::UnixStream::connect(path)
+                        .await
+                        .map_err(connect_error)?,
+                )
+                .await
+            }
+            #[cfg(windows)]
+            Endpoint::NamedPipe(name) => {
+                let pipe = tokio::net::windows::named_pipe::ClientOptions::new()
+                    .open(name)
+                    .map_err(connect_error)?;
+                handshake(pipe).await
+            }
+            Endpoint::Tcp {
+                address,
+                tls: false,
+            } => handshake(TcpStream::connect(address).await.map_err(connect_error)?).await,
+            endpoint => Err(CommandError::Unsupported(format!(
+                "Unsupported transport for '{}' on this platform",
+                endpoint
+            ))),
+        }
+    }
+}
+
+async fn handshake<S>(stream: S) -> Result<SendRequest<RequestBody>, CommandError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to connect: {}", e)))?;
+    tauri::async_runtime::spawn(connection);
+    Ok(sender)
+}
+
+/// Percent-encodes a query parameter value.
+pub fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn to_json<B: Serialize>(body: &B) -> Result<Vec<u8>, CommandError> {
+    serde_json::to_vec(body)
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to encode request: {}", e)))
+}
+
+pub(crate) async fn read_body(body: Incoming) -> Result<Bytes, CommandError> {
+    body.collect()
+        .await
+        .map(|collected| collected.to_bytes())
+        .map_err(|e| CommandError::DockerError(format!("Failed to read response: {}", e)))
+}
+
+pub(crate) async fn parse_json<T: DeserializeOwned>(body: Incoming) -> Result<T, CommandError> {
+    let body = read_body(body).await?;
+    serde_json::from_slice(&body)
+        .map_err(|e| CommandError::UnexpectedError(format!("Unexpected response: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_docker_hosts() {
+        assert_eq!(
+            Endpoint::parse_with_tls("unix:///var/run/docker.sock", false),
+            Some(Endpoint::Unix(PathBuf::from("/var/run/docker.sock")))
+        );
+        assert_eq!(
+            Endpoint::parse_with_tls("npipe:////./pipe/docker_engine", false),
+            Some(Endpoint::NamedPipe(r"\\.\pipe\docker_engine".to_string()))
+        );
+        assert_eq!(
+            Endpoint::parse_with_tls("tcp://10.0.0.5:2375/", false),
+            Some(Endpoint::Tcp {
+                address: "10.0.0.5:2375".to_string(),
+                tls: false
+            })
+        );
+        assert_eq!(
+            Endpoint::parse_with_tls("https://10.0.0.5:2376", false),
+            Some(Endpoint::Tcp {
+                address: "10.0.0.5:2376".to_string(),
+                tls: true
+            })
+        );
+    }
+
+    #[test]
+    fn tls_verify_upgrades_tcp_hosts() {
+        assert_eq!(
+            Endpoint::parse_with_tls("tcp://10.0.0.5:2376", true),
+            Some(Endpoint::Tcp {
+                address: "10.0.0.5:2376".to_string(),
+                tls: true
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_other_hosts_unparsed() {
+        for host in ["ssh://user@host", "fd://", "/var/run/docker.sock"] {
+            assert_eq!(Endpoint::parse_with_tls(host, false), None, "{}", host);
+        }
+    }
+
+    #[test]
+    fn displays_endpoints_as_hosts() {
+        for host in [
+            "unix:///var/run/docker.sock",
+            "npipe:////./pipe/docker_engine",
+            "tcp://10.0.0.5:2375",
+            "https://10.0.0.5:2376",
+        ] {
+            let endpoint = Endpoint::parse_with_tls(host, false).unwrap();
+            assert_eq!(endpoint.to_string(), host);
+        }
+    }
+}
@@ -8,4 +8,8 @@ pub enum CommandError {
     DockerError(String),
     #[error("Unexpected error: {0}")]
     UnexpectedError(String),
+    #[error("Operation cancelled: {0}")]
+    Cancelled(String),
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
 }
\ No newline at end of file
@@ -1,29 +1,77 @@
 use bollard::container::{
-    Config, CreateContainerOptions, KillContainerOptions, ListContainersOptions, LogsOptions,
-    StopContainerOptions,
+    AttachContainerOptions, AttachContainerResults, Config, CreateContainerOptions,
+    KillContainerOptions, ListContainersOptions, LogOutput, LogsOptions, StopContainerOptions,
+    TopOptions,
+};
+use bollard::image::{
+    CommitContainerOptions, CreateImageOptions, ListImagesOptions, RemoveImageOptions,
 };
-use bollard::image::{CreateImageOptions, ListImagesOptions, RemoveImageOptions};
 use bollard::network::{
     ConnectNetworkOptions, CreateNetworkOptions, DisconnectNetworkOptions,
     ListNetworksOptions,
 };
-use bollard::secret::HostConfig;
+use bollard::secret::{ChangeType, EndpointIpamConfig, EndpointSettings, HealthConfig, HostConfig};
 use bollard::volume::ListVolumesOptions;
 use bollard::Docker;
 use futures_util::StreamExt;
-use payload::{Network, NetworkContainer, ProgressInfo, Volume};
+use payload::{
+    IpamConfig, Network, NetworkContainer, NetworkDetails, NetworkEndpoint, ProgressInfo,
+    PullProgress, Volume,
+};
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
 
+use crate::engine::{Endpoint, EngineClient};
 use crate::error::CommandError;
-use crate::payload::{Container, Image};
+use crate::operations::OperationRegistry;
+use crate::payload::{
+    CommittedImage, Container, ContainerDetails, ContainerExit, ContainerHealth, ContainerOutput,
+    ContainerProcesses, EngineProfile, Feature, FileChangeKind, FileChangeNode, HealthProbe,
+    HealthcheckSpec, Image, NetworkEndpointOptions, PullPolicy,
+};
+use crate::progress::PullAggregator;
+use crate::reference::{validate_platform, ImageReference};
+use crate::registry::RegistryClient;
+use crate::settings::SettingsStore;
+use crate::templates::TemplateStore;
 use tauri::ipc::Channel;
-use tauri::{Emitter, State};
-
+use tauri::{Emitter, Manager, State};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::OnceCell;
+
+mod batch;
+mod compat;
+mod copy;
+mod engine;
 mod error;
+mod operations;
 mod payload;
+mod ports;
+mod progress;
+mod recreate;
+mod reference;
+mod registry;
+mod secrets;
+mod settings;
+#[cfg(unix)]
+mod ssh;
+mod swarm;
+mod system;
+mod templates;
+mod topology;
+mod updates;
+mod watch;
 
 struct AppState {
     docker: Docker,
+    engine: EngineClient,
+    watched_containers: Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>,
+    operations: OperationRegistry,
+    profile: OnceCell<EngineProfile>,
+    settings: SettingsStore,
+    templates: TemplateStore,
+    registry: RegistryClient,
 }
 
 #[tauri::command]
@@ -46,6 +94,7 @@ async fn list_containers(state: State<'_, AppState>) -> Result<Vec<Container>, C
                     .first()
                     .map(|name| name.strip_prefix('/').unwrap_or(name).to_owned())
             }),
+            health: item.status.as_deref().and_then(health_from_status),
             status: item.status,
             state: item.state,
             ports: item
@@ -57,9 +106,142 @@ async fn list_containers(state: State<'_, AppState>) -> Result<Vec<Container>, C
     Ok(result)
 }
 
+/// The list endpoint has no `State.Health`, but the daemon appends it to the
+/// status line, e.g. `Up 2 minutes (health: starting)`.
+fn health_from_status(status: &str) -> Option<String> {
+    let (_, health) = status.rsplit_once('(')?;
+    let health = health.strip_suffix(')')?;
+    let health = health.strip_prefix("health: ").unwrap_or(health);
+
+    matches!(health, "starting" | "healthy" | "unhealthy").then(|| health.to_string())
+}
+
 #[tauri::command]
-async fn list_images(state: State<'_, AppState>) -> Result<Vec<Image>, CommandError> {
+async fn inspect_container(
+    state: State<'_, AppState>,
+    container_name: &str,
+) -> Result<ContainerDetails, CommandError> {
+    let docker = &state.docker;
+
+    let container = docker
+        .inspect_container(container_name, None)
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to inspect container: {}", e)))?;
+    let container_state = container.state.unwrap_or_default();
+
+    let health = container_state.health.map(|health| ContainerHealth {
+        status: health
+            .status
+            .map(|status| status.to_string())
+            .unwrap_or_default(),
+        failing_streak: health.failing_streak.unwrap_or_default(),
+        log: health
+            .log
+            .unwrap_or_default()
+            .into_iter()
+            .map(|probe| HealthProbe {
+                start: probe.start,
+                end: probe.end,
+                exit_code: probe.exit_code,
+                output: probe.output,
+            })
+            .collect(),
+    });
+
+    Ok(ContainerDetails {
+        id: container.id,
+        name: container
+            .name
+            .map(|name| name.strip_prefix('/').unwrap_or(&name).to_owned()),
+        image: container.config.and_then(|config| config.image),
+        status: container_state.status.map(|status| status.to_string()),
+        exit_code: container_state.exit_code,
+        oom_killed: container_state.oom_killed,
+        started_at: container_state.started_at,
+        finished_at: container_state.finished_at,
+        health,
+    })
+}
+
+#[tauri::command]
+async fn container_changes(
+    state: State<'_, AppState>,
+    container_name: &str,
+) -> Result<FileChangeNode, CommandError> {
+    let docker = &state.docker;
+
+    let mut changes = docker
+        .container_changes(container_name)
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to list container changes: {}", e)))?
+        .unwrap_or_default();
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut root = FileChangeNode {
+        name: "/".to_string(),
+        path: "/".to_string(),
+        kind: None,
+        children: Vec::new(),
+    };
+
+    for change in changes {
+        let kind = match change.kind {
+            ChangeType::_0 => FileChangeKind::Modified,
+            ChangeType::_1 => FileChangeKind::Added,
+            ChangeType::_2 => FileChangeKind::Deleted,
+        };
+        let mut node = &mut root;
+        for component in change.path.split('/').filter(|c| !c.is_empty()) {
+            let index = match node.children.iter().position(|c| c.name == component) {
+                Some(index) => index,
+                None => {
+                    node.children.push(FileChangeNode {
+                        name: component.to_string(),
+                        path: format!("{}/{}", node.path.trim_end_matches('/'), component),
+                        kind: None,
+                        children: Vec::new(),
+                    });
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[index];
+        }
+        node.kind = Some(kind);
+    }
+
+    Ok(root)
+}
+
+#[tauri::command]
+async fn container_top(
+    state: State<'_, AppState>,
+    container_name: &str,
+    ps_args: Option<String>,
+) -> Result<ContainerProcesses, CommandError> {
     let docker = &state.docker;
+
+    let top = docker
+        .top_processes(
+            container_name,
+            Some(TopOptions {
+                ps_args: ps_args.unwrap_or_else(|| "-ef".to_string()),
+            }),
+        )
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to list processes: {}", e)))?;
+
+    Ok(ContainerProcesses {
+        titles: top.titles.unwrap_or_default(),
+        processes: top.processes.unwrap_or_default(),
+    })
+}
+
+#[tauri::command]
+async fn list_images(state: State<'_, AppState>) -> Result<Vec<Image>, CommandError> {
+    list_images_inner(&state.docker).await
+}
+
+async fn list_images_inner(docker: &Docker) -> Result<Vec<Image>, CommandError> {
     let images = docker
         .list_images(Some(ListImagesOptions::<String> {
             all: true,
@@ -79,17 +261,124 @@ async fn list_images(state: State<'_, AppState>) -> Result<Vec<Image>, CommandEr
     Ok(result)
 }
 
+const COMMIT_CHANGE_INSTRUCTIONS: [&str; 10] = [
+    "CMD",
+    "ENTRYPOINT",
+    "ENV",
+    "EXPOSE",
+    "LABEL",
+    "ONBUILD",
+    "STOPSIGNAL",
+    "USER",
+    "VOLUME",
+    "WORKDIR",
+];
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn commit_container(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    container_name: &str,
+    repo: String,
+    tag: Option<String>,
+    author: Option<String>,
+    message: Option<String>,
+    pause: Option<bool>,
+    changes: Option<Vec<String>>,
+) -> Result<CommittedImage, CommandError> {
+    let docker = &state.docker;
+
+    if repo.trim().is_empty() {
+        return Err(CommandError::UnexpectedError(
+            "A repository name is required to commit a container".to_string(),
+        ));
+    }
+    let tag = tag
+        .filter(|tag| !tag.trim().is_empty())
+        .unwrap_or_else(|| "latest".to_string());
+
+    let changes = changes.unwrap_or_default();
+    for change in &changes {
+        let instruction = change.split_whitespace().next().unwrap_or_default();
+        if !COMMIT_CHANGE_INSTRUCTIONS.contains(&instruction.to_uppercase().as_str()) {
+            return Err(CommandError::UnexpectedError(format!(
+                "Unsupported commit change '{}': expected one of {}",
+                change,
+                COMMIT_CHANGE_INSTRUCTIONS.join(", ")
+            )));
+        }
+    }
+
+    let options = CommitContainerOptions {
+        container: container_name.to_string(),
+        repo: repo.clone(),
+        tag: tag.clone(),
+        comment: message.unwrap_or_default(),
+        author: author.unwrap_or_default(),
+        pause: pause.unwrap_or(true),
+        changes: (!changes.is_empty()).then(|| changes.join("\n")),
+    };
+
+    docker
+        .commit_container(options, Config::<String>::default())
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to commit container '{}': {}",
+                container_name, e
+            ))
+        })?;
+
+    let repo_tag = format!("{}:{}", repo, tag);
+    let image = docker.inspect_image(&repo_tag).await.map_err(|e| {
+        CommandError::DockerError(format!("Failed to inspect committed image: {}", e))
+    })?;
+
+    let images = list_images_inner(docker).await?;
+    app_handle.emit("images-changed", images).map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to send image list update: {}", e))
+    })?;
+
+    Ok(CommittedImage {
+        id: image.id.unwrap_or_default(),
+        repo_tag,
+    })
+}
+
 #[tauri::command]
 async fn emit_logs(
     state: State<'_, AppState>,
     container_name: &str,
     on_event: Channel<String>,
+    operation_id: Option<String>,
+) -> Result<(), CommandError> {
+    state
+        .operations
+        .run(
+            operation_id,
+            "logs",
+            container_name,
+            emit_logs_inner(
+                &state.docker,
+                container_name,
+                state.settings.get().log_tail,
+                on_event,
+            ),
+        )
+        .await
+}
+
+async fn emit_logs_inner(
+    docker: &Docker,
+    container_name: &str,
+    tail: String,
+    on_event: Channel<String>,
 ) -> Result<(), CommandError> {
-    let docker = &state.docker;
     let options = Some(LogsOptions::<String> {
         stdout: true,
         stderr: true,
-        tail: "all".parse().unwrap(),
+        tail,
         ..Default::default()
     });
 
@@ -115,50 +404,70 @@ async fn emit_logs(
 
 #[tauri::command]
 async fn create_container(
+    app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
     image: String,
     port_mapping: Option<String>,
+    healthcheck: Option<HealthcheckSpec>,
+    pull_policy: Option<PullPolicy>,
 ) -> Result<(), CommandError> {
     let docker = &state.docker;
+    let mut config = container_config(image.clone(), port_mapping)?;
+    ensure_image(&app_handle, docker, &image, pull_policy.unwrap_or_default()).await?;
 
-    let port_bindings = port_mapping
-        .as_ref()
-        .map(|mapping| {
-            let parts: Vec<&str> = mapping.split(':').collect();
-            if parts.len() != 2 {
-                return HashMap::new();
-            }
+    config.healthcheck = healthcheck.map(health_config).transpose()?;
 
-            let mut port_bindings = HashMap::new();
-            let host_port = parts[0];
-            let container_port = parts[1];
+    let response = docker
+        .create_container(None::<CreateContainerOptions<String>>, config)
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to create container: {}", e)))?;
 
-            let host_binding = vec![bollard::service::PortBinding {
-                host_ip: Some("0.0.0.0".to_string()),
-                host_port: Some(host_port.to_string()),
-            }];
-            port_bindings.insert(format!("{}/tcp", container_port), Some(host_binding));
+    docker
+        .start_container::<String>(&response.id, None)
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to start container: {}", e)))?;
 
-            port_bindings
-        })
-        .unwrap_or_default();
+    Ok(())
+}
 
-    let exposed_ports = port_mapping
-        .map(|mapping| {
-            let parts: Vec<&str> = mapping.split(':').collect();
-            if parts.len() != 2 {
-                return HashMap::new();
+async fn ensure_image(
+    app_handle: &tauri::AppHandle,
+    docker: &Docker,
+    image: &str,
+    pull_policy: PullPolicy,
+) -> Result<(), CommandError> {
+    let should_pull = match pull_policy {
+        PullPolicy::Always => true,
+        PullPolicy::Never => false,
+        PullPolicy::Missing => match docker.inspect_image(image).await {
+            Ok(_) => false,
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => true,
+            Err(e) => {
+                return Err(CommandError::DockerError(format!(
+                    "Failed to inspect image '{}': {}",
+                    image, e
+                )))
             }
+        },
+    };
 
-            let mut exposed_ports = HashMap::new();
-            let container_port = parts[1];
-            exposed_ports.insert(format!("{}/tcp", container_port), HashMap::new());
+    if should_pull {
+        let reference = ImageReference::parse(image)?;
+        pull_image_inner(app_handle, docker, &reference, None, None).await?;
+    }
 
-            exposed_ports
-        })
-        .unwrap_or_default();
+    Ok(())
+}
 
-    let config = Config {
+fn container_config(
+    image: String,
+    port_mapping: Option<String>,
+) -> Result<Config<String>, CommandError> {
+    let (exposed_ports, port_bindings) = ports::port_config(port_mapping.as_deref())?;
+
+    Ok(Config {
         image: Some(image),
         exposed_ports: Some(exposed_ports),
         host_config: Some(HostConfig {
@@ -166,25 +475,163 @@ async fn create_container(
             ..Default::default()
         }),
         ..Default::default()
+    })
+}
+
+fn health_config(spec: HealthcheckSpec) -> Result<HealthConfig, CommandError> {
+    const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+    let test = match spec.test.first().map(String::as_str) {
+        None => {
+            return Err(CommandError::UnexpectedError(
+                "Healthcheck test must not be empty".to_string(),
+            ))
+        }
+        Some("NONE") | Some("CMD") | Some("CMD-SHELL") => spec.test,
+        Some(_) => vec!["CMD-SHELL".to_string(), spec.test.join(" ")],
     };
+    let seconds = |name: &str, value: Option<u64>| {
+        value
+            .map(|value| {
+                i64::try_from(value)
+                    .ok()
+                    .and_then(|value| value.checked_mul(NANOS_PER_SECOND))
+                    .ok_or_else(|| {
+                        CommandError::UnexpectedError(format!(
+                            "Healthcheck {} of {} seconds is too large",
+                            name, value
+                        ))
+                    })
+            })
+            .transpose()
+    };
+
+    Ok(HealthConfig {
+        test: Some(test),
+        interval: seconds("interval", spec.interval_seconds)?,
+        timeout: seconds("timeout", spec.timeout_seconds)?,
+        retries: spec.retries,
+        start_period: seconds("start period", spec.start_period_seconds)?,
+        ..Default::default()
+    })
+}
+
+/// Runs a one-off container the way `docker run --rm` does: output is streamed
+/// to `on_output` while it runs, and the container removes itself on exit.
+#[tauri::command]
+async fn run_container(
+    state: State<'_, AppState>,
+    image: String,
+    command: Option<Vec<String>>,
+    port_mapping: Option<String>,
+    stdin: Option<String>,
+    on_output: Channel<ContainerOutput>,
+) -> Result<ContainerExit, CommandError> {
+    let docker = &state.docker;
+
+    let mut config = container_config(image, port_mapping)?;
+    config.cmd = command;
+    config.attach_stdout = Some(true);
+    config.attach_stderr = Some(true);
+    config.attach_stdin = Some(stdin.is_some());
+    config.open_stdin = Some(stdin.is_some());
+    config.stdin_once = Some(stdin.is_some());
+    if let Some(host_config) = config.host_config.as_mut() {
+        host_config.auto_remove = Some(true);
+    }
 
     let response = docker
         .create_container(None::<CreateContainerOptions<String>>, config)
         .await
         .map_err(|e| CommandError::DockerError(format!("Failed to create container: {}", e)))?;
-
-    docker
-        .start_container::<String>(&response.id, None)
+    let id = response.id;
+
+    let AttachContainerResults {
+        mut output,
+        mut input,
+    } = docker
+        .attach_container(
+            &id,
+            Some(AttachContainerOptions::<String> {
+                stdin: Some(stdin.is_some()),
+                stdout: Some(true),
+                stderr: Some(true),
+                stream: Some(true),
+                ..Default::default()
+            }),
+        )
         .await
-        .map_err(|e| CommandError::DockerError(format!("Failed to start container: {}", e)))?;
+        .map_err(|e| CommandError::DockerError(format!("Failed to attach to container: {}", e)))?;
 
-    Ok(())
+    // The wait has to be in place before the container starts, otherwise a
+    // short-lived container can be gone before we ask for its exit code.
+    let exit = match watch::register_wait(docker, &state.engine, &id, "removed").await {
+        Ok(exit) => exit,
+        Err(e) => {
+            let _ = delete_container_inner(docker, &id).await;
+            return Err(e);
+        }
+    };
+    let waiter = tauri::async_runtime::spawn(exit);
+
+    if let Err(e) = docker.start_container::<String>(&id, None).await {
+        waiter.abort();
+        let _ = delete_container_inner(docker, &id).await;
+        return Err(CommandError::DockerError(format!(
+            "Failed to start container: {}",
+            e
+        )));
+    }
+
+    let streamed = async {
+        if let Some(stdin) = stdin {
+            input.write_all(stdin.as_bytes()).await.map_err(|e| {
+                CommandError::UnexpectedError(format!("Failed to write stdin: {}", e))
+            })?;
+            input.shutdown().await.map_err(|e| {
+                CommandError::UnexpectedError(format!("Failed to close stdin: {}", e))
+            })?;
+        }
+
+        while let Some(chunk) = output.next().await {
+            let chunk = chunk.map_err(|e| {
+                CommandError::DockerError(format!("Failed to read container output: {}", e))
+            })?;
+            let stream = match chunk {
+                LogOutput::StdErr { .. } => "stderr",
+                _ => "stdout",
+            };
+            on_output
+                .send(ContainerOutput {
+                    stream: stream.to_string(),
+                    message: chunk.to_string(),
+                })
+                .map_err(|e| {
+                    CommandError::UnexpectedError(format!("Failed to emit output: {}", e))
+                })?;
+        }
+
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = streamed {
+        waiter.abort();
+        let _ = delete_container_inner(docker, &id).await;
+        return Err(e);
+    }
+
+    waiter.await.map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to wait for container: {}", e))
+    })?
 }
 
 #[tauri::command]
 async fn remove_image(state: State<'_, AppState>, image: &str) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    remove_image_inner(&state.docker, image).await
+}
 
+async fn remove_image_inner(docker: &Docker, image: &str) -> Result<(), CommandError> {
     let options = RemoveImageOptions {
         force: true,
         ..Default::default()
@@ -220,8 +667,10 @@ async fn start_container(
     state: State<'_, AppState>,
     container_name: &str,
 ) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    start_container_inner(&state.docker, container_name).await
+}
 
+async fn start_container_inner(docker: &Docker, container_name: &str) -> Result<(), CommandError> {
     match docker.start_container::<String>(container_name, None).await {
         Ok(_) => {
             println!("Started container '{}' successfully.", container_name);
@@ -253,7 +702,10 @@ async fn kill_container(
     state: State<'_, AppState>,
     container_name: &str,
 ) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    kill_container_inner(&state.docker, container_name).await
+}
+
+async fn kill_container_inner(docker: &Docker, container_name: &str) -> Result<(), CommandError> {
     let options = KillContainerOptions { signal: "SIGKILL" };
 
     match docker.kill_container(container_name, Some(options)).await {
@@ -287,8 +739,10 @@ async fn delete_container(
     state: State<'_, AppState>,
     container_name: &str,
 ) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    delete_container_inner(&state.docker, container_name).await
+}
 
+async fn delete_container_inner(docker: &Docker, container_name: &str) -> Result<(), CommandError> {
     match docker
         .remove_container(
             container_name,
@@ -330,8 +784,16 @@ async fn stop_container(
     state: State<'_, AppState>,
     container_name: &str,
 ) -> Result<(), CommandError> {
-    let docker = &state.docker;
-    let options = StopContainerOptions { t: 10 };
+    let timeout = state.settings.get().stop_timeout_seconds;
+    stop_container_inner(&state.docker, container_name, timeout).await
+}
+
+async fn stop_container_inner(
+    docker: &Docker,
+    container_name: &str,
+    timeout: i64,
+) -> Result<(), CommandError> {
+    let options = StopContainerOptions { t: timeout };
 
     match docker.stop_container(container_name, Some(options)).await {
         Ok(_) => {
@@ -413,8 +875,10 @@ async fn list_volumes(state: State<'_, AppState>) -> Result<Vec<Volume>, Command
 
 #[tauri::command]
 async fn remove_volume(state: State<'_, AppState>, volume_name: &str) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    remove_volume_inner(&state.docker, volume_name).await
+}
 
+async fn remove_volume_inner(docker: &Docker, volume_name: &str) -> Result<(), CommandError> {
     match docker.remove_volume(volume_name, None).await {
         Ok(_) => {
             println!("Volume '{}' removed successfully.", volume_name);
@@ -446,8 +910,10 @@ async fn pause_container(
     state: State<'_, AppState>,
     container_name: &str,
 ) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    pause_container_inner(&state.docker, container_name).await
+}
 
+async fn pause_container_inner(docker: &Docker, container_name: &str) -> Result<(), CommandError> {
     docker
         .pause_container(container_name)
         .await
@@ -473,8 +939,10 @@ async fn unpause_container(
 
 #[tauri::command]
 async fn list_networks(state: State<'_, AppState>) -> Result<Vec<Network>, CommandError> {
-    let docker = &state.docker;
+    list_networks_inner(&state.docker).await
+}
 
+async fn list_networks_inner(docker: &Docker) -> Result<Vec<Network>, CommandError> {
     let networks = docker
         .list_networks(Some(ListNetworksOptions::<String> {
             ..Default::default()
@@ -508,7 +976,7 @@ async fn create_network(
 ) -> Result<(), CommandError> {
     let docker = &state.docker;
 
-    let driv = driver.unwrap_or_else(|| "bridge".to_string());
+    let driv = driver.unwrap_or_else(|| state.settings.get().default_network_driver);
 
     let options = CreateNetworkOptions {
         name: name.as_str(),
@@ -536,8 +1004,8 @@ async fn list_network_containers(
         .inspect_network(
             network_name,
             Some(InspectNetworkOptions {
-                verbose: true,
-                scope: "global",
+                verbose: false,
+                scope: "",
             }),
         )
         .await
@@ -565,10 +1033,95 @@ async fn list_network_containers(
 }
 
 
+#[tauri::command]
+async fn inspect_network(
+    state: State<'_, AppState>,
+    network_name: &str,
+) -> Result<NetworkDetails, CommandError> {
+    inspect_network_inner(&state.docker, network_name).await
+}
+
+async fn inspect_network_inner(
+    docker: &Docker,
+    network_name: &str,
+) -> Result<NetworkDetails, CommandError> {
+    use bollard::network::InspectNetworkOptions;
+
+    // An empty scope matches local bridge networks as well as swarm-scoped ones.
+    let network = docker
+        .inspect_network(
+            network_name,
+            Some(InspectNetworkOptions {
+                verbose: false,
+                scope: "",
+            }),
+        )
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to inspect network: {}", e)))?;
+
+    let network_key = network.name.clone().unwrap_or_default();
+    let endpoints = network.containers.unwrap_or_default();
+    let containers = futures_util::future::join_all(endpoints.into_iter().map(|(id, endpoint)| {
+        let network_key = network_key.clone();
+        async move {
+            // Aliases only live on the container side of the endpoint.
+            let aliases = docker
+                .inspect_container(&id, None)
+                .await
+                .ok()
+                .and_then(|container| container.network_settings)
+                .and_then(|settings| settings.networks)
+                .and_then(|mut networks| networks.remove(&network_key))
+                .and_then(|settings| settings.aliases)
+                .unwrap_or_default();
+
+            NetworkEndpoint {
+                container_id: id,
+                name: endpoint.name.unwrap_or_else(|| "Unnamed".to_string()),
+                endpoint_id: endpoint.endpoint_id,
+                ipv4_address: endpoint.ipv4_address.filter(|ip| !ip.is_empty()),
+                ipv6_address: endpoint.ipv6_address.filter(|ip| !ip.is_empty()),
+                mac_address: endpoint.mac_address.filter(|mac| !mac.is_empty()),
+                aliases,
+            }
+        }
+    }))
+    .await;
+
+    let ipam = network.ipam.unwrap_or_default();
+
+    Ok(NetworkDetails {
+        id: network.id.unwrap_or_default(),
+        name: network_key,
+        driver: network.driver,
+        scope: network.scope,
+        created: network.created,
+        internal: network.internal,
+        attachable: network.attachable,
+        enable_ipv6: network.enable_ipv6,
+        ipam_driver: ipam.driver,
+        ipam: ipam
+            .config
+            .unwrap_or_default()
+            .into_iter()
+            .map(|config| IpamConfig {
+                subnet: config.subnet,
+                gateway: config.gateway,
+                ip_range: config.ip_range,
+            })
+            .collect(),
+        options: network.options.unwrap_or_default(),
+        labels: network.labels.unwrap_or_default(),
+        containers,
+    })
+}
+
 #[tauri::command]
 async fn remove_network(state: State<'_, AppState>, network_id: &str) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    remove_network_inner(&state.docker, network_id).await
+}
 
+async fn remove_network_inner(docker: &Docker, network_id: &str) -> Result<(), CommandError> {
     match docker.remove_network(network_id).await {
         Ok(_) => {
             println!("Network '{}' removed successfully.", network_id);
@@ -600,15 +1153,80 @@ async fn connect_container_to_network(
     state: State<'_, AppState>,
     container_id: &str,
     network_id: &str,
+    endpoint: Option<NetworkEndpointOptions>,
 ) -> Result<(), CommandError> {
     let docker = &state.docker;
+    let NetworkEndpointOptions {
+        ipv4_address,
+        ipv6_address,
+        aliases,
+        links,
+    } = endpoint.unwrap_or_default();
+
+    let links = links.filter(|links| !links.is_empty());
+    if links.is_some() {
+        compat::require_feature(&state, Feature::NetworkLinks).await?;
+    }
+
+    let ipv4_address = ipv4_address.filter(|ip| !ip.is_empty());
+    let ipv6_address = ipv6_address.filter(|ip| !ip.is_empty());
+
+    if ipv4_address.is_some() || ipv6_address.is_some() {
+        let network = inspect_network_inner(docker, network_id).await?;
+        let subnets: Vec<&str> = network
+            .ipam
+            .iter()
+            .filter_map(|config| config.subnet.as_deref())
+            .collect();
+
+        let addresses = [(&ipv4_address, false), (&ipv6_address, true)];
+        for (address, ipv6) in addresses {
+            let Some(address) = address else {
+                continue;
+            };
+            let ip: IpAddr = address.parse().map_err(|_| {
+                CommandError::UnexpectedError(format!("'{}' is not a valid IP address", address))
+            })?;
+            if ip.is_ipv6() != ipv6 {
+                return Err(CommandError::UnexpectedError(format!(
+                    "'{}' is not an {} address",
+                    address,
+                    if ipv6 { "IPv6" } else { "IPv4" }
+                )));
+            }
+            if !subnets.iter().any(|subnet| ip_in_subnet(ip, subnet)) {
+                return Err(CommandError::UnexpectedError(format!(
+                    "{} is outside the configured subnets of network '{}' ({})",
+                    address,
+                    network.name,
+                    if subnets.is_empty() {
+                        "none".to_string()
+                    } else {
+                        subnets.join(", ")
+                    }
+                )));
+            }
+        }
+    }
+
+    let ipam_config =
+        (ipv4_address.is_some() || ipv6_address.is_some()).then(|| EndpointIpamConfig {
+            ipv4_address,
+            ipv6_address,
+            ..Default::default()
+        });
 
     docker
         .connect_network(
             network_id,
             ConnectNetworkOptions {
                 container: container_id,
-                ..Default::default()
+                endpoint_config: EndpointSettings {
+                    ipam_config,
+                    aliases: aliases.filter(|aliases| !aliases.is_empty()),
+                    links,
+                    ..Default::default()
+                },
             },
         )
         .await
@@ -619,24 +1237,111 @@ async fn connect_container_to_network(
     Ok(())
 }
 
+/// Checks whether `ip` falls inside a CIDR block such as `172.20.0.0/16`.
+fn ip_in_subnet(ip: IpAddr, subnet: &str) -> bool {
+    let Some((network, prefix)) = subnet.split_once('/') else {
+        return false;
+    };
+    let (Ok(network), Ok(prefix)) = (network.parse::<IpAddr>(), prefix.parse::<u32>()) else {
+        return false;
+    };
+
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) if prefix <= 32 => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) if prefix <= 128 => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn pull_image(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
     image_name: String,
-) -> Result<(), CommandError> {
-    let docker = &state.docker;
+    tag: Option<String>,
+    digest: Option<String>,
+    platform: Option<String>,
+    on_progress: Option<Channel<PullProgress>>,
+    operation_id: Option<String>,
+) -> Result<Option<String>, CommandError> {
+    let mut reference = ImageReference::parse(&image_name)?;
+    if let Some(tag) = tag.filter(|tag| !tag.is_empty()) {
+        reference.tag = Some(tag);
+    }
+    if let Some(digest) = digest.filter(|digest| !digest.is_empty()) {
+        reference.digest = Some(digest);
+    }
+    // Re-parse so that the tag and digest overrides get validated as well.
+    let reference = ImageReference::parse(&reference.to_string())?;
+
+    let platform = platform.filter(|platform| !platform.is_empty());
+    if let Some(platform) = &platform {
+        validate_platform(platform)?;
+    }
+
+    state
+        .operations
+        .run(
+            operation_id,
+            "pull",
+            &reference.to_string(),
+            pull_image_inner(
+                &app_handle,
+                &state.docker,
+                &reference,
+                platform.as_deref(),
+                on_progress.as_ref(),
+            ),
+        )
+        .await
+}
+
+/// Pulls `reference` and returns the digest the registry resolved it to.
+async fn pull_image_inner(
+    app_handle: &tauri::AppHandle,
+    docker: &Docker,
+    reference: &ImageReference,
+    platform: Option<&str>,
+    on_progress: Option<&Channel<PullProgress>>,
+) -> Result<Option<String>, CommandError> {
+    let name = reference.name();
+    // The daemon takes either a tag or a digest here, and pulls every tag of
+    // the repository when it is left empty.
+    let tag = reference
+        .digest
+        .clone()
+        .or_else(|| reference.tag.clone())
+        .unwrap_or_else(|| "latest".to_string());
 
     let options = Some(CreateImageOptions {
-        from_image: image_name.as_str(),
+        from_image: name.as_str(),
+        tag: tag.as_str(),
+        platform: platform.unwrap_or_default(),
         ..Default::default()
     });
 
     let mut pull_stream = docker.create_image(options, None, None);
+    let mut aggregator = PullAggregator::new(&reference.to_string());
 
     while let Some(result) = pull_stream.next().await {
         match result {
             Ok(output) => {
+                aggregator.update(&output);
+                if on_progress.is_some() {
+                    if let Some(summary) = aggregator.throttled_summary() {
+                        send_pull_progress(on_progress, summary)?;
+                    }
+                    continue;
+                }
+
+                // Without a channel, fall back to the per-layer `pull-progress` event.
                 if let Ok(progress) = serde_json::from_value::<ProgressInfo>(
                     serde_json::to_value(output).unwrap_or_default(),
                 ) {
@@ -657,8 +1362,43 @@ async fn pull_image(
         }
     }
 
-    Ok(())
+    send_pull_progress(on_progress, aggregator.summary(true))?;
+
+    if let Some(digest) = aggregator.digest() {
+        return Ok(Some(digest.to_string()));
+    }
+
+    let pulled = match reference.digest {
+        Some(_) => format!("{}@{}", name, tag),
+        None => format!("{}:{}", name, tag),
+    };
+    let repo_digests = docker
+        .inspect_image(&pulled)
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to inspect pulled image: {}", e)))?
+        .repo_digests
+        .unwrap_or_default();
+
+    Ok(repo_digests
+        .iter()
+        .find(|repo_digest| repo_digest.starts_with(&format!("{}@", name)))
+        .or_else(|| repo_digests.first())
+        .and_then(|repo_digest| repo_digest.split_once('@'))
+        .map(|(_, digest)| digest.to_string()))
+}
+
+fn send_pull_progress(
+    on_progress: Option<&Channel<PullProgress>>,
+    summary: PullProgress,
+) -> Result<(), CommandError> {
+    match on_progress {
+        Some(channel) => channel.send(summary).map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to send pull progress: {}", e))
+        }),
+        None => Ok(()),
+    }
 }
+
 #[tauri::command]
 async fn disconnect_container_from_network(
     state: State<'_, AppState>,
@@ -691,17 +1431,80 @@ async fn disconnect_container_from_network(
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .manage(AppState {
-            docker: Docker::connect_with_local_defaults().unwrap(),
+        .setup(|app| {
+            let config_dir = app.path().app_config_dir()?;
+            let settings = SettingsStore::default();
+            settings.load(config_dir.join(settings::SETTINGS_FILE));
+            let templates = TemplateStore::default();
+            templates.load(config_dir.join(templates::TEMPLATES_FILE));
+
+            // The configured host wins over `DOCKER_HOST`.
+            let docker_host = settings
+                .get()
+                .docker_host
+                .or_else(|| std::env::var("DOCKER_HOST").ok());
+            #[cfg(unix)]
+            let (endpoint, tunnel) =
+                match docker_host.as_deref().and_then(ssh::SshTunnel::from_host) {
+                    Some(Ok(tunnel)) => (
+                        Endpoint::Unix(tunnel.local_socket().to_path_buf()),
+                        Some(tunnel),
+                    ),
+                    Some(Err(e)) => {
+                        println!("Ignoring Docker host: {}", e);
+                        (compat::resolve_endpoint(None), None)
+                    }
+                    None => (compat::resolve_endpoint(docker_host.as_deref()), None),
+                };
+            #[cfg(not(unix))]
+            let endpoint = compat::resolve_endpoint(docker_host.as_deref());
+
+            let (endpoint, docker) = match compat::connect(&endpoint) {
+                Ok(docker) => (endpoint, docker),
+                Err(e) => {
+                    println!("{}; falling back to the local engine", e);
+                    let endpoint = compat::resolve_endpoint(None);
+                    let docker = compat::connect(&endpoint)?;
+                    (endpoint, docker)
+                }
+            };
+            app.manage(AppState {
+                docker: docker.clone(),
+                engine: EngineClient::new(endpoint),
+                watched_containers: Mutex::new(HashMap::new()),
+                operations: OperationRegistry::default(),
+                profile: OnceCell::new(),
+                settings,
+                templates,
+                registry: RegistryClient::default(),
+            });
+
+            let app_handle = app.handle().clone();
+            let negotiate_docker = docker.clone();
+            tauri::async_runtime::spawn(async move {
+                // Until the tunnel is up, commands fail with a connection error.
+                #[cfg(unix)]
+                if let Some(tunnel) = tunnel {
+                    tunnel.start(app_handle.clone()).await;
+                }
+                system::negotiate_api_version(app_handle, negotiate_docker).await;
+            });
+            tauri::async_runtime::spawn(watch::monitor_health(app.handle().clone(), docker));
+            Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             list_containers,
+            inspect_container,
             list_images,
+            container_changes,
+            container_top,
+            commit_container,
             emit_logs,
             kill_container,
             stop_container,
             remove_image,
             create_container,
+            run_container,
             create_volume,
             remove_volume,
             disconnect_container_from_network,
@@ -715,8 +1518,126 @@ pub fn run() {
             delete_container,
             pull_image,
             list_volumes,
-            list_network_containers
+            list_network_containers,
+            inspect_network,
+            topology::network_topology,
+            topology::export_network_topology,
+            batch::start_containers,
+            batch::stop_containers,
+            batch::kill_containers,
+            batch::delete_containers,
+            batch::pause_containers,
+            batch::remove_images,
+            batch::remove_volumes,
+            batch::remove_networks,
+            copy::copy_from_container,
+            copy::copy_to_container,
+            copy::export_container,
+            copy::import_image,
+            watch::wait_container,
+            watch::watch_container,
+            watch::unwatch_container,
+            operations::cancel_operation,
+            operations::list_operations,
+            swarm::swarm_status,
+            swarm::init_swarm,
+            swarm::leave_swarm,
+            swarm::list_services,
+            swarm::create_service,
+            swarm::update_service,
+            swarm::scale_service,
+            swarm::remove_service,
+            swarm::list_service_tasks,
+            swarm::list_nodes,
+            swarm::service_logs,
+            secrets::list_secrets,
+            secrets::inspect_secret,
+            secrets::create_secret,
+            secrets::remove_secret,
+            secrets::list_configs,
+            secrets::inspect_config,
+            secrets::create_config,
+            secrets::remove_config,
+            secrets::attach_secret_to_service,
+            secrets::detach_secret_from_service,
+            secrets::attach_config_to_service,
+            secrets::detach_config_from_service,
+            system::system_info,
+            system::system_version,
+            compat::engine_profile,
+            settings::get_settings,
+            settings::update_settings,
+            templates::list_templates,
+            templates::save_template,
+            templates::delete_template,
+            templates::instantiate_template,
+            templates::export_templates,
+            templates::import_templates,
+            recreate::recreate_container,
+            updates::check_image_updates,
+            updates::update_container,
+            registry::search_images,
+            registry::list_remote_tags
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app_handle, _event| {
+            #[cfg(unix)]
+            if let tauri::RunEvent::Exit = _event {
+                ssh::remove_local_socket();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(address: &str) -> IpAddr {
+        address.parse().unwrap()
+    }
+
+    #[test]
+    fn matches_ipv4_subnets() {
+        assert!(ip_in_subnet(ip("172.20.5.9"), "172.20.0.0/16"));
+        assert!(ip_in_subnet(ip("10.0.0.1"), "10.0.0.1/32"));
+        assert!(ip_in_subnet(ip("192.168.1.1"), "0.0.0.0/0"));
+        assert!(!ip_in_subnet(ip("172.21.0.1"), "172.20.0.0/16"));
+        assert!(!ip_in_subnet(ip("10.0.0.2"), "10.0.0.1/32"));
+    }
+
+    #[test]
+    fn matches_ipv6_subnets() {
+        assert!(ip_in_subnet(ip("fd00:1::42"), "fd00:1::/64"));
+        assert!(ip_in_subnet(ip("2001:db8::1"), "::/0"));
+        assert!(!ip_in_subnet(ip("fd00:2::42"), "fd00:1::/64"));
+    }
+
+    #[test]
+    fn rejects_mismatched_families_and_bad_subnets() {
+        assert!(!ip_in_subnet(ip("172.20.0.1"), "fd00::/8"));
+        assert!(!ip_in_subnet(ip("fd00::1"), "172.20.0.0/16"));
+        assert!(!ip_in_subnet(ip("172.20.0.1"), "172.20.0.0"));
+        assert!(!ip_in_subnet(ip("172.20.0.1"), "172.20.0.0/33"));
+        assert!(!ip_in_subnet(ip("fd00::1"), "fd00::/129"));
+        assert!(!ip_in_subnet(ip("172.20.0.1"), "not-a-subnet/16"));
+    }
+
+    #[test]
+    fn rejects_healthcheck_durations_that_overflow() {
+        let spec = |interval_seconds| HealthcheckSpec {
+            test: vec!["curl -f http://localhost".to_string()],
+            interval_seconds: Some(interval_seconds),
+            timeout_seconds: None,
+            retries: None,
+            start_period_seconds: None,
+        };
+
+        assert_eq!(
+            health_config(spec(30)).unwrap().interval,
+            Some(30_000_000_000)
+        );
+        assert!(health_config(spec(u64::MAX / 1_000_000_000 + 1)).is_err());
+        assert!(health_config(spec(u64::MAX)).is_err());
+    }
 }
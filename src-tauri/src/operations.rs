@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures_util::future::{AbortHandle, Abortable};
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::payload::OperationInfo;
+use crate::AppState;
+
+struct RunningOperation {
+    info: OperationInfo,
+    abort_handle: AbortHandle,
+}
+
+/// Tracks long-running streaming commands so the frontend can list and
+/// cancel them while they are in flight.
+#[derive(Default)]
+pub struct OperationRegistry {
+    next_id: AtomicU64,
+    running: Mutex<HashMap<String, RunningOperation>>,
+}
+
+/// Drops the registry entry once the operation finishes, is cancelled, or the
+/// command future itself is dropped.
+struct Registration<'a> {
+    registry: &'a OperationRegistry,
+    id: String,
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut running) = self.registry.running.lock() {
+            running.remove(&self.id);
+        }
+    }
+}
+
+impl OperationRegistry {
+    /// Runs `operation` under `id`, or a freshly generated one that callers
+    /// can find through `list_operations`. A cancelled operation resolves to
+    /// `CommandError::Cancelled` with its ID.
+    pub async fn run<T, F>(
+        &self,
+        id: Option<String>,
+        kind: &str,
+        target: &str,
+        operation: F,
+    ) -> Result<T, CommandError>
+    where
+        F: Future<Output = Result<T, CommandError>>,
+    {
+        let id = id
+            .unwrap_or_else(|| format!("op-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1));
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+
+        {
+            let mut running = self.running.lock().map_err(|e| {
+                CommandError::UnexpectedError(format!("Failed to register operation: {}", e))
+            })?;
+            if running.contains_key(&id) {
+                return Err(CommandError::UnexpectedError(format!(
+                    "Operation '{}' is already running",
+                    id
+                )));
+            }
+            running.insert(
+                id.clone(),
+                RunningOperation {
+                    info: OperationInfo {
+                        id: id.clone(),
+                        kind: kind.to_string(),
+                        target: target.to_string(),
+                        started_at: SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map(|elapsed| elapsed.as_secs())
+                            .unwrap_or_default(),
+                    },
+                    abort_handle,
+                },
+            );
+        }
+        let _registration = Registration {
+            registry: self,
+            id: id.clone(),
+        };
+
+        match Abortable::new(operation, abort_registration).await {
+            Ok(result) => result,
+            Err(_) => Err(CommandError::Cancelled(id)),
+        }
+    }
+
+    pub fn cancel(&self, id: &str) -> Result<(), CommandError> {
+        let running = self.running.lock().map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to read operations: {}", e))
+        })?;
+
+        match running.get(id) {
+            Some(operation) => {
+                operation.abort_handle.abort();
+                Ok(())
+            }
+            None => Err(CommandError::UnexpectedError(format!(
+                "Operation '{}' is not running",
+                id
+            ))),
+        }
+    }
+
+    pub fn list(&self) -> Result<Vec<OperationInfo>, CommandError> {
+        let running = self.running.lock().map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to read operations: {}", e))
+        })?;
+
+        let mut operations: Vec<OperationInfo> = running
+            .values()
+            .map(|operation| operation.info.clone())
+            .collect();
+        operations.sort_by_key(|operation| operation.started_at);
+
+        Ok(operations)
+    }
+}
+
+#[tauri::command]
+pub async fn cancel_operation(state: State<'_, AppState>, id: &str) -> Result<(), CommandError> {
+    state.operations.cancel(id)
+}
+
+#[tauri::command]
+pub async fn list_operations(
+    state: State<'_, AppState>,
+) -> Result<Vec<OperationInfo>, CommandError> {
+    state.operations.list()
+}
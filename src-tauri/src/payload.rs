@@ -17,10 +17,11 @@ pub struct Container {
     pub name : Option<String>,
     pub status : Option<String>,
     pub state : Option<String>,
+    pub health : Option<String>,
     pub ports : Option<Vec<String>>
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Image{
     pub repo_tag : String,
     pub size : i64
@@ -78,4 +79,500 @@ pub struct NetworkContainer {
     pub id: String,
     pub name: String,
     pub network_id: Option<std::string::String>,
-}
\ No newline at end of file
+}
+#[derive(Debug, serde::Serialize)]
+pub struct BatchItemResult {
+    pub name: String,
+    pub success: bool,
+    pub error: Option<crate::error::CommandError>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CopyProgress {
+    pub bytes_transferred: u64,
+    pub total_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Modified,
+    Added,
+    Deleted,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileChangeNode {
+    pub name: String,
+    pub path: String,
+    pub kind: Option<FileChangeKind>,
+    pub children: Vec<FileChangeNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContainerProcesses {
+    pub titles: Vec<String>,
+    pub processes: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommittedImage {
+    pub id: String,
+    pub repo_tag: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerExit {
+    pub container: String,
+    pub status_code: i64,
+    pub error: Option<String>,
+    pub oom_killed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerOutput {
+    pub stream: String,
+    pub message: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct HealthcheckSpec {
+    pub test: Vec<String>,
+    pub interval_seconds: Option<u64>,
+    pub timeout_seconds: Option<u64>,
+    pub retries: Option<i64>,
+    pub start_period_seconds: Option<u64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct HealthProbe {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub exit_code: Option<i64>,
+    pub output: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ContainerHealth {
+    pub status: String,
+    pub failing_streak: i64,
+    pub log: Vec<HealthProbe>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ContainerDetails {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub status: Option<String>,
+    pub exit_code: Option<i64>,
+    pub oom_killed: Option<bool>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub health: Option<ContainerHealth>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ContainerHealthEvent {
+    pub container_id: String,
+    pub container_name: Option<String>,
+    pub status: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PullPolicy {
+    Always,
+    #[default]
+    Missing,
+    Never,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OperationInfo {
+    pub id: String,
+    pub kind: String,
+    pub target: String,
+    pub started_at: u64,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LayerPhase {
+    Waiting,
+    Downloading,
+    Extracting,
+    Complete,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LayerProgress {
+    pub id: String,
+    pub phase: LayerPhase,
+    pub current: i64,
+    pub total: Option<i64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PullProgress {
+    pub image: String,
+    pub layers: Vec<LayerProgress>,
+    pub percent: f64,
+    pub downloaded_bytes: i64,
+    pub total_bytes: i64,
+    pub bytes_per_second: f64,
+    pub eta_seconds: Option<u64>,
+    pub digest: Option<String>,
+    pub complete: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct NetworkEndpoint {
+    pub container_id: String,
+    pub name: String,
+    pub endpoint_id: Option<String>,
+    pub ipv4_address: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub mac_address: Option<String>,
+    pub aliases: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NetworkDetails {
+    pub id: String,
+    pub name: String,
+    pub driver: Option<String>,
+    pub scope: Option<String>,
+    pub created: Option<String>,
+    pub internal: Option<bool>,
+    pub attachable: Option<bool>,
+    pub enable_ipv6: Option<bool>,
+    pub ipam_driver: Option<String>,
+    pub ipam: Vec<IpamConfig>,
+    pub options: HashMap<String, String>,
+    pub labels: HashMap<String, String>,
+    pub containers: Vec<NetworkEndpoint>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TopologyNodeKind {
+    Network,
+    Container,
+    Host,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TopologyNode {
+    pub id: String,
+    pub kind: TopologyNodeKind,
+    pub label: String,
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TopologyEdge {
+    pub source: String,
+    pub target: String,
+    pub ipv4_address: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub aliases: Vec<String>,
+    pub ports: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct NetworkTopology {
+    pub nodes: Vec<TopologyNode>,
+    pub edges: Vec<TopologyEdge>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TopologyFormat {
+    Json,
+    Dot,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SwarmStatus {
+    pub local_node_state: String,
+    pub is_manager: bool,
+    pub node_id: Option<String>,
+    pub nodes: Option<i64>,
+    pub managers: Option<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServiceDefinition {
+    pub name: String,
+    pub image: String,
+    pub replicas: Option<i64>,
+    pub global: Option<bool>,
+    pub command: Option<Vec<String>>,
+    pub env: Option<Vec<String>>,
+    pub ports: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ServiceSummary {
+    pub id: String,
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub mode: String,
+    pub replicas: Option<i64>,
+    pub running_tasks: Option<u64>,
+    pub desired_tasks: Option<u64>,
+    pub ports: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TaskSummary {
+    pub id: String,
+    pub slot: Option<i64>,
+    pub node_id: Option<String>,
+    pub state: Option<String>,
+    pub desired_state: Option<String>,
+    pub message: Option<String>,
+    pub error: Option<String>,
+    pub container_id: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NodeSummary {
+    pub id: String,
+    pub hostname: Option<String>,
+    pub role: Option<String>,
+    pub availability: Option<String>,
+    pub state: Option<String>,
+    pub address: Option<String>,
+    pub leader: bool,
+    pub engine_version: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SecretSummary {
+    pub id: String,
+    pub name: Option<String>,
+    pub labels: HashMap<String, String>,
+    pub driver: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConfigSummary {
+    pub id: String,
+    pub name: Option<String>,
+    pub labels: HashMap<String, String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConfigDetails {
+    #[serde(flatten)]
+    pub summary: ConfigSummary,
+    pub data: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EngineInfo {
+    pub name: Option<String>,
+    pub server_version: Option<String>,
+    pub operating_system: Option<String>,
+    pub os_type: Option<String>,
+    pub architecture: Option<String>,
+    pub kernel_version: Option<String>,
+    pub storage_driver: Option<String>,
+    pub cgroup_driver: Option<String>,
+    pub cgroup_version: Option<String>,
+    pub rootless: bool,
+    pub security_options: Vec<String>,
+    pub cpus: Option<i64>,
+    pub memory_total: Option<i64>,
+    pub index_server_address: Option<String>,
+    pub registry_mirrors: Vec<String>,
+    pub insecure_registries: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EngineComponent {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EngineVersion {
+    pub version: Option<String>,
+    pub api_version: Option<String>,
+    pub min_api_version: Option<String>,
+    pub negotiated_api_version: String,
+    pub required_api_version: String,
+    pub api_supported: bool,
+    pub os: Option<String>,
+    pub arch: Option<String>,
+    pub kernel_version: Option<String>,
+    pub go_version: Option<String>,
+    pub git_commit: Option<String>,
+    pub build_time: Option<String>,
+    pub components: Vec<EngineComponent>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ApiVersionWarning {
+    pub negotiated_api_version: String,
+    pub required_api_version: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EngineKind {
+    Docker,
+    Podman,
+    Colima,
+    RancherDesktop,
+    #[serde(rename = "orbstack")]
+    OrbStack,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Feature {
+    Swarm,
+    NetworkLinks,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FeatureSupport {
+    pub feature: Feature,
+    pub supported: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EngineProfile {
+    pub kind: EngineKind,
+    pub socket_path: String,
+    pub version: Option<String>,
+    pub api_version: Option<String>,
+    pub features: Vec<FeatureSupport>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TunnelState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SshTunnelStatus {
+    pub destination: String,
+    pub state: TunnelState,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub stop_timeout_seconds: i64,
+    pub log_tail: String,
+    pub refresh_interval_seconds: u64,
+    pub default_network_driver: String,
+    pub docker_host: Option<String>,
+}
+
+/// Partial update for `Settings`; fields left out keep their current value.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct SettingsPatch {
+    pub stop_timeout_seconds: Option<i64>,
+    pub log_tail: Option<String>,
+    pub refresh_interval_seconds: Option<u64>,
+    pub default_network_driver: Option<String>,
+    /// An empty string clears the host so `DOCKER_HOST` applies again.
+    pub docker_host: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContainerTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub image: String,
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+    #[serde(default)]
+    pub ports: Vec<String>,
+    #[serde(default)]
+    pub env: Vec<String>,
+    #[serde(default)]
+    pub volumes: Vec<String>,
+    #[serde(default)]
+    pub network: Option<String>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TemplateInfo {
+    #[serde(flatten)]
+    pub template: ContainerTemplate,
+    pub variables: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TemplateImportResult {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ContainerPatch {
+    pub image_tag: Option<String>,
+    pub ports: Option<Vec<String>>,
+    pub env: HashMap<String, Option<String>>,
+    pub mounts: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ContainerUpdateStatus {
+    pub container_id: String,
+    pub container_name: Option<String>,
+    pub image: String,
+    pub local_digest: Option<String>,
+    pub remote_digest: Option<String>,
+    pub outdated: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ImageSearchResult {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub stars: Option<i64>,
+    pub official: bool,
+    pub automated: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RemoteTagPage {
+    pub repository: String,
+    pub tags: Vec<String>,
+    pub next: Option<String>,
+}
+
+/// Per-endpoint options for connecting a container to a network.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct NetworkEndpointOptions {
+    pub ipv4_address: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub aliases: Option<Vec<String>>,
+    pub links: Option<Vec<String>>,
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use bollard::models::PortBinding;
+
+use crate::error::CommandError;
+
+pub(crate) type ExposedPorts = HashMap<String, HashMap<(), ()>>;
+pub(crate) type PortBindings = HashMap<String, Option<Vec<PortBinding>>>;
+
+/// Parses `host:container[/protocol]` into the container port key (e.g.
+/// `80/tcp`) and the host port.
+pub(crate) fn parse_port(mapping: &str) -> Result<(String, String), CommandError> {
+    let invalid = || {
+        CommandError::UnexpectedError(format!(
+            "Invalid port mapping '{}': expected host:container[/protocol]",
+            mapping
+        ))
+    };
+
+    let (ports, protocol) = mapping.split_once('/').unwrap_or((mapping, "tcp"));
+    let (host_port, container_port) = ports.split_once(':').ok_or_else(invalid)?;
+    host_port.parse::<u16>().map_err(|_| invalid())?;
+    container_port.parse::<u16>().map_err(|_| invalid())?;
+    if !["tcp", "udp", "sctp"].contains(&protocol) {
+        return Err(invalid());
+    }
+
+    Ok((
+        format!("{}/{}", container_port, protocol),
+        host_port.to_string(),
+    ))
+}
+
+/// Builds the exposed ports and host bindings for `mappings`, publishing
+/// each on all interfaces.
+pub(crate) fn port_config<'a>(
+    mappings: impl IntoIterator<Item = &'a str>,
+) -> Result<(ExposedPorts, PortBindings), CommandError> {
+    let mut exposed_ports = HashMap::new();
+    let mut port_bindings = HashMap::new();
+    for mapping in mappings {
+        let (container_port, host_port) = parse_port(mapping)?;
+        exposed_ports.insert(container_port.clone(), HashMap::new());
+        port_bindings.insert(
+            container_port,
+            Some(vec![PortBinding {
+                host_ip: Some("0.0.0.0".to_string()),
+                host_port: Some(host_port),
+            }]),
+        );
+    }
+
+    Ok((exposed_ports, port_bindings))
+}
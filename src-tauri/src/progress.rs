@@ -0,0 +1,265 @@
+use std::time::{Duration, Instant};
+
+use bollard::models::CreateImageInfo;
+
+use crate::payload::{LayerPhase, LayerProgress, PullProgress};
+
+/// Minimum delay between two summaries sent for the same pull.
+const EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+struct LayerState {
+    id: String,
+    phase: LayerPhase,
+    downloaded: i64,
+    download_total: Option<i64>,
+    extracted: i64,
+    extract_total: Option<i64>,
+}
+
+impl LayerState {
+    /// Download and extraction each count for half of a layer's progress.
+    fn fraction(&self) -> f64 {
+        let ratio = |current: i64, total: Option<i64>| match total {
+            Some(total) if total > 0 => (current as f64 / total as f64).clamp(0.0, 1.0),
+            _ => 0.0,
+        };
+
+        match self.phase {
+            LayerPhase::Waiting => 0.0,
+            LayerPhase::Downloading => 0.5 * ratio(self.downloaded, self.download_total),
+            LayerPhase::Extracting => 0.5 + 0.5 * ratio(self.extracted, self.extract_total),
+            LayerPhase::Complete => 1.0,
+        }
+    }
+}
+
+/// Folds the raw `CreateImageInfo` stream of a single pull into per-layer
+/// state and turns it into throttled `PullProgress` summaries.
+pub struct PullAggregator {
+    image: String,
+    layers: Vec<LayerState>,
+    digest: Option<String>,
+    started: Instant,
+    last_emit: Option<Instant>,
+}
+
+impl PullAggregator {
+    pub fn new(image: &str) -> Self {
+        PullAggregator {
+            image: image.to_string(),
+            layers: Vec::new(),
+            digest: None,
+            started: Instant::now(),
+            last_emit: None,
+        }
+    }
+
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    pub fn update(&mut self, info: &CreateImageInfo) {
+        let status = info.status.as_deref().unwrap_or_default();
+
+        if let Some(digest) = status.strip_prefix("Digest: ") {
+            self.digest = Some(digest.trim().to_string());
+            return;
+        }
+
+        // Layer events carry the short layer ID; image-level status lines
+        // either have no ID or use the tag as ID ("Pulling from ...").
+        let Some(id) = info.id.as_deref() else {
+            return;
+        };
+        if status.starts_with("Pulling from") {
+            return;
+        }
+
+        let index = match self.layers.iter().position(|layer| layer.id == id) {
+            Some(index) => index,
+            None => {
+                self.layers.push(LayerState {
+                    id: id.to_string(),
+                    phase: LayerPhase::Waiting,
+                    downloaded: 0,
+                    download_total: None,
+                    extracted: 0,
+                    extract_total: None,
+                });
+                self.layers.len() - 1
+            }
+        };
+        let layer = &mut self.layers[index];
+        let detail = info.progress_detail.as_ref();
+        let current = detail.and_then(|detail| detail.current);
+        let total = detail.and_then(|detail| detail.total);
+
+        match status {
+            "Downloading" => {
+                layer.phase = LayerPhase::Downloading;
+                layer.downloaded = current.unwrap_or(layer.downloaded);
+                layer.download_total = total.or(layer.download_total);
+            }
+            "Verifying Checksum" | "Download complete" => {
+                layer.phase = LayerPhase::Downloading;
+                if let Some(total) = layer.download_total {
+                    layer.downloaded = total;
+                }
+            }
+            "Extracting" => {
+                layer.phase = LayerPhase::Extracting;
+                if let Some(total) = layer.download_total {
+                    layer.downloaded = total;
+                }
+                layer.extracted = current.unwrap_or(layer.extracted);
+                layer.extract_total = total.or(layer.extract_total);
+            }
+            "Pull complete" | "Already exists" => {
+                layer.phase = LayerPhase::Complete;
+                if let Some(total) = layer.download_total {
+                    layer.downloaded = total;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns a summary if enough time has passed since the previous one.
+    pub fn throttled_summary(&mut self) -> Option<PullProgress> {
+        let now = Instant::now();
+        if self
+            .last_emit
+            .is_some_and(|last| now.duration_since(last) < EMIT_INTERVAL)
+        {
+            return None;
+        }
+        self.last_emit = Some(now);
+        Some(self.summary(false))
+    }
+
+    pub fn summary(&self, complete: bool) -> PullProgress {
+        let downloaded_bytes: i64 = self.layers.iter().map(|layer| layer.downloaded).sum();
+        let total_bytes: i64 = self
+            .layers
+            .iter()
+            .filter_map(|layer| layer.download_total)
+            .sum();
+
+        let percent = if complete {
+            100.0
+        } else if self.layers.is_empty() {
+            0.0
+        } else {
+            let fractions: f64 = self.layers.iter().map(LayerState::fraction).sum();
+            100.0 * fractions / self.layers.len() as f64
+        };
+
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let bytes_per_second = if elapsed > 0.0 {
+            downloaded_bytes as f64 / elapsed
+        } else {
+            0.0
+        };
+        let eta_seconds = (!complete && bytes_per_second > 0.0 && total_bytes >= downloaded_bytes)
+            .then(|| ((total_bytes - downloaded_bytes) as f64 / bytes_per_second).ceil() as u64);
+
+        PullProgress {
+            image: self.image.clone(),
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| {
+                    let (current, total) = match layer.phase {
+                        LayerPhase::Extracting => (layer.extracted, layer.extract_total),
+                        _ => (layer.downloaded, layer.download_total),
+                    };
+                    LayerProgress {
+                        id: layer.id.clone(),
+                        phase: layer.phase,
+                        current,
+                        total,
+                    }
+                })
+                .collect(),
+            percent,
+            downloaded_bytes,
+            total_bytes,
+            bytes_per_second,
+            eta_seconds,
+            digest: self.digest.clone(),
+            complete,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::models::ProgressDetail;
+
+    use super::*;
+
+    fn info(id: Option<&str>, status: &str, progress: Option<(i64, i64)>) -> CreateImageInfo {
+        CreateImageInfo {
+            id: id.map(str::to_string),
+            status: Some(status.to_string()),
+            progress_detail: progress.map(|(current, total)| ProgressDetail {
+                current: Some(current),
+                total: Some(total),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tracks_layers_through_each_phase() {
+        let mut aggregator = PullAggregator::new("nginx:latest");
+        aggregator.update(&info(Some("latest"), "Pulling from library/nginx", None));
+        aggregator.update(&info(Some("a"), "Pulling fs layer", None));
+        aggregator.update(&info(Some("b"), "Already exists", None));
+        aggregator.update(&info(Some("a"), "Downloading", Some((50, 100))));
+
+        let summary = aggregator.summary(false);
+        assert_eq!(summary.layers.len(), 2);
+        assert_eq!(summary.layers[0].phase, LayerPhase::Downloading);
+        assert_eq!(summary.layers[1].phase, LayerPhase::Complete);
+        assert_eq!(summary.downloaded_bytes, 50);
+        assert_eq!(summary.total_bytes, 100);
+        // Layer a is a quarter done (half of its download), layer b is done.
+        assert_eq!(summary.percent, 62.5);
+
+        aggregator.update(&info(Some("a"), "Extracting", Some((30, 120))));
+        let summary = aggregator.summary(false);
+        assert_eq!(summary.layers[0].phase, LayerPhase::Extracting);
+        assert_eq!(summary.layers[0].current, 30);
+        assert_eq!(summary.layers[0].total, Some(120));
+        assert_eq!(summary.downloaded_bytes, 100);
+
+        aggregator.update(&info(Some("a"), "Pull complete", None));
+        assert_eq!(aggregator.summary(false).percent, 100.0);
+    }
+
+    #[test]
+    fn records_the_digest() {
+        let mut aggregator = PullAggregator::new("nginx:latest");
+        aggregator.update(&info(None, "Digest: sha256:abc ", None));
+        aggregator.update(&info(None, "Status: Downloaded newer image", None));
+
+        assert_eq!(aggregator.digest(), Some("sha256:abc"));
+        let summary = aggregator.summary(true);
+        assert!(summary.complete);
+        assert_eq!(summary.percent, 100.0);
+        assert_eq!(summary.eta_seconds, None);
+        assert_eq!(summary.digest.as_deref(), Some("sha256:abc"));
+    }
+
+    #[test]
+    fn throttles_summaries() {
+        let mut aggregator = PullAggregator::new("nginx:latest");
+
+        assert!(aggregator.throttled_summary().is_some());
+        assert!(aggregator.throttled_summary().is_none());
+
+        aggregator.last_emit = Some(Instant::now() - EMIT_INTERVAL);
+        assert!(aggregator.throttled_summary().is_some());
+    }
+}
@@ -0,0 +1,522 @@
+use std::collections::HashMap;
+
+use bollard::container::{
+    Config, CreateContainerOptions, InspectContainerOptions, NetworkingConfig,
+    RemoveContainerOptions, RenameContainerOptions,
+};
+use bollard::models::{
+    ContainerConfig, ContainerInspectResponse, EndpointSettings, ImageConfig, MountPointTypeEnum,
+};
+use bollard::network::ConnectNetworkOptions;
+use bollard::Docker;
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::payload::{ContainerPatch, PullPolicy};
+use crate::ports::port_config;
+use crate::reference::ImageReference;
+use crate::{ensure_image, start_container_inner, stop_container_inner, AppState};
+
+/// Replaces a container with one built from its current configuration plus
+/// `patch`, keeping its name, networks and anonymous volumes. If the new
+/// container cannot be created or started, the original is put back.
+#[tauri::command]
+pub async fn recreate_container(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    container_name: &str,
+    patch: ContainerPatch,
+) -> Result<String, CommandError> {
+    recreate_container_inner(&app_handle, &state, container_name, patch).await
+}
+
+pub(crate) async fn recreate_container_inner(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    container_name: &str,
+    patch: ContainerPatch,
+) -> Result<String, CommandError> {
+    let docker = &state.docker;
+    let current = docker
+        .inspect_container(container_name, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to inspect container '{}': {}",
+                container_name, e
+            ))
+        })?;
+
+    let old_id = current.id.clone().unwrap_or_default();
+    let name = current
+        .name
+        .as_deref()
+        .unwrap_or(container_name)
+        .trim_start_matches('/')
+        .to_string();
+    ensure_recreatable(&current, &name)?;
+    let was_running = current
+        .state
+        .as_ref()
+        .and_then(|state| state.running)
+        .unwrap_or(false);
+
+    // The old image may be gone; then everything in the config carries over.
+    let image_config = match &current.image {
+        Some(image) => docker
+            .inspect_image(image)
+            .await
+            .ok()
+            .and_then(|image| image.config),
+        None => None,
+    };
+
+    let (config, extra_networks) = recreated_config(&current, image_config.as_ref(), patch)?;
+    if let Some(image) = &config.image {
+        ensure_image(app_handle, docker, image, PullPolicy::Missing).await?;
+    }
+
+    if was_running {
+        let timeout = state.settings.get().stop_timeout_seconds;
+        stop_container_inner(docker, &old_id, timeout).await?;
+    }
+
+    let backup_name = format!("{}-{}", name, &old_id[..old_id.len().min(12)]);
+    if let Err(e) = rename(docker, &old_id, &backup_name).await {
+        if was_running {
+            let _ = start_container_inner(docker, &old_id).await;
+        }
+        return Err(e);
+    }
+
+    match create_and_start(docker, &name, config, extra_networks).await {
+        Ok(new_id) => {
+            docker
+                .remove_container(
+                    &old_id,
+                    Some(RemoveContainerOptions {
+                        force: true,
+                        ..Default::default()
+                    }),
+                )
+                .await
+                .map_err(|e| {
+                    CommandError::DockerError(format!(
+                        "Recreated '{}' but failed to remove the previous container '{}': {}",
+                        name, backup_name, e
+                    ))
+                })?;
+
+            Ok(new_id)
+        }
+        Err((new_id, e)) => {
+            rollback(docker, new_id.as_deref(), &old_id, &name, was_running)
+                .await
+                .map_err(|rollback_error| {
+                    CommandError::DockerError(format!(
+                        "{}; rolling back also failed, the original container is kept as '{}': {}",
+                        e, backup_name, rollback_error
+                    ))
+                })?;
+
+            Err(CommandError::DockerError(format!(
+                "{}; the original container was restored",
+                e
+            )))
+        }
+    }
+}
+
+/// Creates the replacement, attaches it to the remaining networks and starts
+/// it. On failure the ID of a partially set up container is handed back so
+/// it can be cleaned up.
+async fn create_and_start(
+    docker: &Docker,
+    name: &str,
+    config: Config<String>,
+    extra_networks: HashMap<String, EndpointSettings>,
+) -> Result<String, (Option<String>, CommandError)> {
+    let response = docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: name.to_string(),
+                platform: None,
+            }),
+            config,
+        )
+        .await
+        .map_err(|e| {
+            (
+                None,
+                CommandError::DockerError(format!("Failed to create container '{}': {}", name, e)),
+            )
+        })?;
+    let new_id = response.id;
+
+    for (network, endpoint_config) in extra_networks {
+        docker
+            .connect_network(
+                &network,
+                ConnectNetworkOptions {
+                    container: new_id.clone(),
+                    endpoint_config,
+                },
+            )
+            .await
+            .map_err(|e| {
+                (
+                    Some(new_id.clone()),
+                    CommandError::DockerError(format!(
+                        "Failed to connect '{}' to network '{}': {}",
+                        name, network, e
+                    )),
+                )
+            })?;
+    }
+
+    start_container_inner(docker, &new_id)
+        .await
+        .map_err(|e| (Some(new_id.clone()), e))?;
+
+    Ok(new_id)
+}
+
+async fn rollback(
+    docker: &Docker,
+    new_id: Option<&str>,
+    old_id: &str,
+    name: &str,
+    was_running: bool,
+) -> Result<(), CommandError> {
+    if let Some(new_id) = new_id {
+        docker
+            .remove_container(
+                new_id,
+                Some(RemoveContainerOptions {
+                    force: true,
+                    v: true,
+                    ..Default::default()
+                }),
+            )
+            .await
+            .map_err(|e| {
+                CommandError::DockerError(format!(
+                    "Failed to remove the new container '{}': {}",
+                    new_id, e
+                ))
+            })?;
+    }
+
+    rename(docker, old_id, name).await?;
+    if was_running {
+        start_container_inner(docker, old_id).await?;
+    }
+
+    Ok(())
+}
+
+async fn rename(docker: &Docker, container: &str, name: &str) -> Result<(), CommandError> {
+    docker
+        .rename_container(container, RenameContainerOptions { name })
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to rename container '{}' to '{}': {}",
+                container, name, e
+            ))
+        })
+}
+
+/// Refuses containers started with `--rm`: stopping one deletes it, leaving
+/// nothing to roll back to if the replacement fails.
+fn ensure_recreatable(current: &ContainerInspectResponse, name: &str) -> Result<(), CommandError> {
+    let auto_remove = current
+        .host_config
+        .as_ref()
+        .and_then(|host_config| host_config.auto_remove)
+        .unwrap_or(false);
+    if auto_remove {
+        return Err(CommandError::Unsupported(format!(
+            "Container '{}' is removed automatically when stopped and cannot be recreated",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Drops the values the container inherited unchanged from its image, so a
+/// new image supplies its own defaults instead of the old image's.
+fn without_image_defaults(mut config: ContainerConfig, image: &ImageConfig) -> ContainerConfig {
+    if let (Some(env), Some(image_env)) = (&mut config.env, &image.env) {
+        env.retain(|entry| !image_env.contains(entry));
+    }
+    if let (Some(labels), Some(image_labels)) = (&mut config.labels, &image.labels) {
+        labels.retain(|key, value| image_labels.get(key) != Some(value));
+    }
+    if let (Some(ports), Some(image_ports)) = (&mut config.exposed_ports, &image.exposed_ports) {
+        ports.retain(|port, _| !image_ports.contains_key(port));
+    }
+    if let (Some(volumes), Some(image_volumes)) = (&mut config.volumes, &image.volumes) {
+        volumes.retain(|volume, _| !image_volumes.contains_key(volume));
+    }
+
+    // An explicit entrypoint makes the engine ignore the image's Cmd, so Cmd
+    // only falls back to the image when the entrypoint does too.
+    if config.entrypoint == image.entrypoint {
+        config.entrypoint = None;
+        if config.cmd == image.cmd {
+            config.cmd = None;
+        }
+    }
+    if config.working_dir == image.working_dir {
+        config.working_dir = None;
+    }
+    if config.user == image.user {
+        config.user = None;
+    }
+
+    config
+}
+
+/// Rebuilds the create request from an inspect response and applies the
+/// patch. The network named by `NetworkMode` goes into the create request;
+/// the others are returned to be connected before the container starts.
+fn recreated_config(
+    current: &ContainerInspectResponse,
+    image: Option<&ImageConfig>,
+    patch: ContainerPatch,
+) -> Result<(Config<String>, HashMap<String, EndpointSettings>), CommandError> {
+    let old_id = current.id.as_deref().unwrap_or_default();
+    let mut container_config = current.config.clone().unwrap_or_default();
+    if let Some(image) = image {
+        container_config = without_image_defaults(container_config, image);
+    }
+    let mut config = Config::from(container_config);
+    let mut host_config = current.host_config.clone().unwrap_or_default();
+
+    // An unset hostname defaults to the short container ID, which must not
+    // carry over to the new container.
+    if config
+        .hostname
+        .as_deref()
+        .is_some_and(|hostname| old_id.starts_with(hostname))
+    {
+        config.hostname = None;
+    }
+
+    if let Some(tag) = patch.image_tag.filter(|tag| !tag.is_empty()) {
+        let image = config.image.as_deref().ok_or_else(|| {
+            CommandError::UnexpectedError("Container has no image to retag".to_string())
+        })?;
+        let mut reference = ImageReference::parse(image)?;
+        reference.tag = Some(tag);
+        reference.digest = None;
+        config.image = Some(reference.to_string());
+    }
+
+    if !patch.env.is_empty() {
+        let mut env: Vec<String> = config
+            .env
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| {
+                let key = entry.split_once('=').map_or(entry.as_str(), |(key, _)| key);
+                !patch.env.contains_key(key)
+            })
+            .collect();
+        env.extend(
+            patch.env.iter().filter_map(|(key, value)| {
+                value.as_ref().map(|value| format!("{}={}", key, value))
+            }),
+        );
+        config.env = Some(env);
+    }
+
+    if let Some(ports) = patch.ports {
+        let (exposed_ports, port_bindings) = port_config(ports.iter().map(String::as_str))?;
+        config
+            .exposed_ports
+            .get_or_insert_with(HashMap::new)
+            .extend(exposed_ports);
+        host_config.port_bindings = Some(port_bindings);
+    }
+
+    if let Some(mounts) = patch.mounts {
+        host_config.binds = Some(mounts);
+        host_config.mounts = None;
+    }
+
+    // Hand anonymous volumes to the new container so their data survives,
+    // unless the patch mounted something else at the same path.
+    let mut binds = host_config.binds.take().unwrap_or_default();
+    let taken = |destination: &str, binds: &[String]| {
+        binds
+            .iter()
+            .any(|bind| bind.split(':').nth(1) == Some(destination))
+            || host_config
+                .mounts
+                .iter()
+                .flatten()
+                .any(|mount| mount.target.as_deref() == Some(destination))
+    };
+    for mount in current.mounts.iter().flatten() {
+        if mount.typ != Some(MountPointTypeEnum::VOLUME) {
+            continue;
+        }
+        if let (Some(volume), Some(destination)) = (&mount.name, &mount.destination) {
+            if !taken(destination, &binds) {
+                binds.push(format!("{}:{}", volume, destination));
+            }
+        }
+    }
+    host_config.binds = Some(binds);
+
+    let mut networks: HashMap<String, EndpointSettings> = current
+        .network_settings
+        .as_ref()
+        .and_then(|settings| settings.networks.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(network, endpoint)| {
+            let short_id = &old_id[..old_id.len().min(12)];
+            let endpoint = EndpointSettings {
+                ipam_config: endpoint.ipam_config,
+                links: endpoint.links,
+                aliases: endpoint.aliases.map(|aliases| {
+                    aliases
+                        .into_iter()
+                        .filter(|alias| alias != short_id)
+                        .collect()
+                }),
+                driver_opts: endpoint.driver_opts,
+                ..Default::default()
+            };
+            (network, endpoint)
+        })
+        .collect();
+
+    let primary = match host_config.network_mode.as_deref() {
+        Some("default") | None => "bridge",
+        Some(mode) => mode,
+    };
+    if let Some(endpoint) = networks.remove(primary) {
+        config.networking_config = Some(NetworkingConfig {
+            endpoints_config: HashMap::from([(primary.to_string(), endpoint)]),
+        });
+    }
+    config.host_config = Some(host_config);
+
+    Ok((config, networks))
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::models::HostConfig;
+
+    use super::*;
+
+    fn strings(values: &[&str]) -> Option<Vec<String>> {
+        Some(values.iter().map(|value| value.to_string()).collect())
+    }
+
+    #[test]
+    fn retag_drops_old_image_defaults() {
+        let image = ImageConfig {
+            env: strings(&["PATH=/usr/bin", "NGINX_VERSION=1.25.3"]),
+            cmd: strings(&["nginx", "-g", "daemon off;"]),
+            entrypoint: strings(&["/docker-entrypoint.sh"]),
+            working_dir: Some("/".to_string()),
+            labels: Some(HashMap::from([(
+                "maintainer".to_string(),
+                "NGINX".to_string(),
+            )])),
+            exposed_ports: Some(HashMap::from([("80/tcp".to_string(), HashMap::new())])),
+            ..Default::default()
+        };
+        let current = ContainerInspectResponse {
+            id: Some("0123456789abcdef".to_string()),
+            config: Some(ContainerConfig {
+                hostname: Some("0123456789ab".to_string()),
+                image: Some("nginx:1.25".to_string()),
+                env: strings(&["PATH=/usr/bin", "NGINX_VERSION=1.25.3", "MODE=prod"]),
+                cmd: image.cmd.clone(),
+                entrypoint: image.entrypoint.clone(),
+                working_dir: Some("/".to_string()),
+                labels: Some(HashMap::from([
+                    ("maintainer".to_string(), "NGINX".to_string()),
+                    ("team".to_string(), "web".to_string()),
+                ])),
+                exposed_ports: Some(HashMap::from([
+                    ("80/tcp".to_string(), HashMap::new()),
+                    ("8443/tcp".to_string(), HashMap::new()),
+                ])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let patch = ContainerPatch {
+            image_tag: Some("1.27".to_string()),
+            ..Default::default()
+        };
+
+        let (config, _) = recreated_config(&current, Some(&image), patch).unwrap();
+
+        assert_eq!(config.image.as_deref(), Some("nginx:1.27"));
+        assert_eq!(config.hostname, None);
+        assert_eq!(config.env, strings(&["MODE=prod"]));
+        assert_eq!(config.cmd, None);
+        assert_eq!(config.entrypoint, None);
+        assert_eq!(config.working_dir, None);
+        assert_eq!(
+            config.labels,
+            Some(HashMap::from([("team".to_string(), "web".to_string())]))
+        );
+        assert_eq!(
+            config.exposed_ports,
+            Some(HashMap::from([("8443/tcp".to_string(), HashMap::new())]))
+        );
+    }
+
+    #[test]
+    fn custom_entrypoint_keeps_its_cmd() {
+        let image = ImageConfig {
+            cmd: strings(&["serve"]),
+            entrypoint: strings(&["/entrypoint.sh"]),
+            ..Default::default()
+        };
+        let current = ContainerInspectResponse {
+            config: Some(ContainerConfig {
+                image: Some("app:1".to_string()),
+                cmd: strings(&["serve"]),
+                entrypoint: strings(&["/debug.sh"]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (config, _) =
+            recreated_config(&current, Some(&image), ContainerPatch::default()).unwrap();
+
+        assert_eq!(config.entrypoint, strings(&["/debug.sh"]));
+        assert_eq!(config.cmd, strings(&["serve"]));
+    }
+
+    #[test]
+    fn auto_remove_containers_are_not_recreated() {
+        let mut current = ContainerInspectResponse {
+            host_config: Some(HostConfig {
+                auto_remove: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ensure_recreatable(&current, "web"),
+            Err(CommandError::Unsupported(_))
+        ));
+
+        current.host_config = Some(HostConfig::default());
+        assert!(ensure_recreatable(&current, "web").is_ok());
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt;
+
+use crate::error::CommandError;
+
+/// A parsed image reference of the form `[registry/]repository[:tag][@digest]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageReference {
+    pub registry: Option<String>,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+impl ImageReference {
+    pub fn parse(reference: &str) -> Result<Self, CommandError> {
+        let invalid = |reason: &str| {
+            CommandError::UnexpectedError(format!(
+                "Invalid image reference '{}': {}",
+                reference, reason
+            ))
+        };
+
+        if reference.is_empty() {
+            return Err(invalid("reference is empty"));
+        }
+
+        let (name, digest) = match reference.split_once('@') {
+            Some((name, digest)) => (name, Some(digest)),
+            None => (reference, None),
+        };
+
+        // A ':' after the last '/' starts the tag; earlier ones belong to a registry port.
+        let (name, tag) = match name.rfind(':') {
+            Some(index) if !name[index..].contains('/') => {
+                (&name[..index], Some(&name[index + 1..]))
+            }
+            _ => (name, None),
+        };
+
+        let (registry, repository) = match name.split_once('/') {
+            Some((first, rest))
+                if first.contains('.') || first.contains(':') || first == "localhost" =>
+            {
+                (Some(first), rest)
+            }
+            _ => (None, name),
+        };
+
+        if let Some(registry) = registry {
+            validate_registry(registry).map_err(|reason| invalid(&reason))?;
+        }
+        if repository.is_empty() {
+            return Err(invalid("repository name is empty"));
+        }
+        for component in repository.split('/') {
+            validate_path_component(component).map_err(|reason| invalid(&reason))?;
+        }
+        if let Some(tag) = tag {
+            validate_tag(tag).map_err(|reason| invalid(&reason))?;
+        }
+        if let Some(digest) = digest {
+            validate_digest(digest).map_err(|reason| invalid(&reason))?;
+        }
+
+        Ok(ImageReference {
+            registry: registry.map(str::to_string),
+            repository: repository.to_string(),
+            tag: tag.map(str::to_string),
+            digest: digest.map(str::to_string),
+        })
+    }
+
+    /// The name without tag or digest, as the daemon expects in `fromImage`.
+    pub fn name(&self) -> String {
+        match &self.registry {
+            Some(registry) => format!("{}/{}", registry, self.repository),
+            None => self.repository.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ImageReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{}", tag)?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_registry(registry: &str) -> Result<(), String> {
+    let (host, port) = match registry.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (registry, None),
+    };
+
+    if host.is_empty()
+        || !host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    {
+        return Err(format!("invalid registry host '{}'", host));
+    }
+    if let Some(port) = port {
+        if port.parse::<u16>().is_err() {
+            return Err(format!("invalid registry port '{}'", port));
+        }
+    }
+
+    Ok(())
+}
+
+/// Path components are lowercase alphanumerics joined by `.`, `_`, `__` or runs of `-`.
+fn validate_path_component(component: &str) -> Result<(), String> {
+    let error = || format!("invalid repository component '{}'", component);
+    let bytes = component.as_bytes();
+
+    if bytes.is_empty() || !is_lower_alnum(bytes[0]) || !is_lower_alnum(bytes[bytes.len() - 1]) {
+        return Err(error());
+    }
+
+    let mut index = 0;
+    while index < bytes.len() {
+        if is_lower_alnum(bytes[index]) {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < bytes.len() && !is_lower_alnum(bytes[index]) {
+            index += 1;
+        }
+        match &component[start..index] {
+            "." | "_" | "__" => {}
+            separator if separator.bytes().all(|b| b == b'-') => {}
+            _ => return Err(error()),
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_tag(tag: &str) -> Result<(), String> {
+    let valid = !tag.is_empty()
+        && tag.len() <= 128
+        && !tag.starts_with(['.', '-'])
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid tag '{}'", tag))
+    }
+}
+
+fn validate_digest(digest: &str) -> Result<(), String> {
+    let error = || format!("invalid digest '{}'", digest);
+    let (algorithm, hex) = digest.split_once(':').ok_or_else(error)?;
+
+    let hex_valid = |len: usize| hex.len() == len && hex.chars().all(|c| c.is_ascii_hexdigit());
+    let valid = match algorithm {
+        "sha256" => hex_valid(64),
+        "sha512" => hex_valid(128),
+        _ => false,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(error())
+    }
+}
+
+/// Accepts `os/arch` or `os/arch/variant`, e.g. `linux/arm64` or `linux/arm/v7`.
+pub fn validate_platform(platform: &str) -> Result<(), CommandError> {
+    let parts: Vec<&str> = platform.split('/').collect();
+    let valid = (2..=3).contains(&parts.len())
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(CommandError::UnexpectedError(format!(
+            "Invalid platform '{}': expected os/arch[/variant]",
+            platform
+        )))
+    }
+}
+
+fn is_lower_alnum(byte: u8) -> bool {
+    byte.is_ascii_lowercase() || byte.is_ascii_digit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "sha256:4bcd2a4dcd4c7fd5ac1e1f4e0b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e";
+
+    #[test]
+    fn parses_reference_parts() {
+        let reference = ImageReference::parse(&format!(
+            "registry.example.com:5000/team/app:1.2@{}",
+            DIGEST
+        ))
+        .unwrap();
+
+        assert_eq!(
+            reference,
+            ImageReference {
+                registry: Some("registry.example.com:5000".to_string()),
+                repository: "team/app".to_string(),
+                tag: Some("1.2".to_string()),
+                digest: Some(DIGEST.to_string()),
+            }
+        );
+        assert_eq!(reference.name(), "registry.example.com:5000/team/app");
+    }
+
+    #[test]
+    fn first_component_is_a_registry_only_when_it_looks_like_a_host() {
+        let reference = ImageReference::parse("library/nginx").unwrap();
+        assert_eq!(reference.registry, None);
+        assert_eq!(reference.repository, "library/nginx");
+
+        let reference = ImageReference::parse("localhost/app").unwrap();
+        assert_eq!(reference.registry.as_deref(), Some("localhost"));
+        assert_eq!(reference.repository, "app");
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for reference in [
+            "nginx".to_string(),
+            "nginx:1.27-alpine".to_string(),
+            "ghcr.io/owner/app:v1.0.0".to_string(),
+            "localhost:5000/app".to_string(),
+            format!("alpine@{}", DIGEST),
+            format!("quay.io/org/tool:2024.01@{}", DIGEST),
+            "my-org/some__name.with-dashes:latest".to_string(),
+        ] {
+            assert_eq!(
+                ImageReference::parse(&reference).unwrap().to_string(),
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_references() {
+        for reference in [
+            "",
+            ":latest",
+            "Nginx",
+            "nginx:",
+            "nginx:-beta",
+            "nginx@sha256:abc",
+            "nginx@md5:4bcd2a4dcd4c7fd5ac1e1f4e0b3c4d5e",
+            "registry.example.com:port/app",
+            "bad_host.example.com/app",
+            "team//app",
+            "team/-app",
+            "team/app.",
+            "team/a...b",
+        ] {
+            assert!(
+                ImageReference::parse(reference).is_err(),
+                "'{}' was accepted",
+                reference
+            );
+        }
+
+        assert!(ImageReference::parse(&format!("nginx:{}", "a".repeat(129))).is_err());
+    }
+
+    #[test]
+    fn validates_platforms() {
+        for platform in [
+            "linux/amd64",
+            "linux/arm64",
+            "linux/arm/v7",
+            "windows/amd64",
+        ] {
+            assert!(validate_platform(platform).is_ok(), "{}", platform);
+        }
+        for platform in [
+            "linux",
+            "linux/",
+            "/amd64",
+            "Linux/AMD64",
+            "linux/arm/v7/extra",
+        ] {
+            assert!(validate_platform(platform).is_err(), "{}", platform);
+        }
+    }
+}
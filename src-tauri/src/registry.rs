@@ -0,0 +1,626 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+use bollard::image::SearchImagesOptions;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, LINK, WWW_AUTHENTICATE};
+use reqwest::{Method, Response, StatusCode};
+use serde::Deserialize;
+use tauri::State;
+use tokio::io::AsyncWriteExt;
+
+use crate::engine::encode;
+use crate::error::CommandError;
+use crate::payload::{ImageSearchResult, RemoteTagPage};
+use crate::reference::ImageReference;
+use crate::AppState;
+
+const DOCKER_HUB: &str = "docker.io";
+const DOCKER_HUB_API: &str = "registry-1.docker.io";
+const DOCKER_HUB_AUTH_KEY: &str = "https://index.docker.io/v1/";
+const DEFAULT_TAG_PAGE_SIZE: u32 = 100;
+const MAX_TAG_PAGE_SIZE: u32 = 1000;
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+/// Username docker stores alongside an identity (refresh) token.
+const IDENTITY_TOKEN_USERNAME: &str = "<token>";
+const OAUTH_CLIENT_ID: &str = "docker-desktop";
+
+#[derive(Clone)]
+struct Credentials {
+    username: String,
+    secret: String,
+}
+
+impl Credentials {
+    fn is_identity_token(&self) -> bool {
+        self.username == IDENTITY_TOKEN_USERNAME
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct DockerConfig {
+    #[serde(default)]
+    auths: HashMap<String, AuthEntry>,
+    #[serde(rename = "credsStore")]
+    creds_store: Option<String>,
+    #[serde(rename = "credHelpers", default)]
+    cred_helpers: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct AuthEntry {
+    auth: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(rename = "identitytoken")]
+    identity_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HelperCredentials {
+    #[serde(rename = "Username")]
+    username: String,
+    #[serde(rename = "Secret")]
+    secret: String,
+}
+
+#[derive(Deserialize)]
+struct TagList {
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// Minimal client for the registry v2 (distribution) API. Credentials come
+/// from `~/.docker/config.json`, including credential stores and helpers,
+/// and bearer tokens are negotiated per request from the registry challenge.
+#[derive(Default)]
+pub struct RegistryClient {
+    http: reqwest::Client,
+}
+
+impl RegistryClient {
+    /// Resolves the manifest digest a tag currently points to, as the daemon
+    /// records it in `RepoDigests` after a pull.
+    pub async fn manifest_digest(
+        &self,
+        reference: &ImageReference,
+    ) -> Result<String, CommandError> {
+        let path = format!(
+            "/v2/{}/manifests/{}",
+            api_repository(reference),
+            reference.tag.as_deref().unwrap_or("latest")
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static(MANIFEST_TYPES));
+
+        let response = self.send(Method::HEAD, reference, &path, headers).await?;
+
+        response
+            .headers()
+            .get("Docker-Content-Digest")
+            .and_then(|digest| digest.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                CommandError::DockerError(format!(
+                    "Registry did not return a digest for '{}'",
+                    reference
+                ))
+            })
+    }
+
+    /// Returns one page of tags and the cursor for the next one, taken from
+    /// the `Link` header the registry sends while more tags remain.
+    pub async fn list_tags(
+        &self,
+        reference: &ImageReference,
+        page_size: u32,
+        last: Option<&str>,
+    ) -> Result<(Vec<String>, Option<String>), CommandError> {
+        let mut path = format!(
+            "/v2/{}/tags/list?n={}",
+            api_repository(reference),
+            page_size
+        );
+        if let Some(last) = last {
+            path.push_str(&format!("&last={}", encode(last)));
+        }
+
+        let response = self
+            .send(Method::GET, reference, &path, HeaderMap::new())
+            .await?;
+        let next = response
+            .headers()
+            .get(LINK)
+            .and_then(|link| link.to_str().ok())
+            .and_then(next_cursor);
+        let list: TagList = response.json().await.map_err(|e| {
+            CommandError::DockerError(format!(
+                "Invalid tag list for '{}': {}",
+                reference.name(),
+                e
+            ))
+        })?;
+
+        Ok((list.tags.unwrap_or_default(), next))
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        reference: &ImageReference,
+        path: &str,
+        headers: HeaderMap,
+    ) -> Result<Response, CommandError> {
+        let host = registry_host(reference);
+        let url = format!("{}://{}{}", scheme(host), api_host(host), path);
+        let request = |authorization: Option<String>| {
+            let mut request = self
+                .http
+                .request(method.clone(), &url)
+                .headers(headers.clone());
+            if let Some(authorization) = authorization {
+                request = request.header(AUTHORIZATION, authorization);
+            }
+            request.send()
+        };
+        let request_error = |e: reqwest::Error| {
+            CommandError::DockerError(format!("Failed to reach registry '{}': {}", host, e))
+        };
+
+        let mut response = request(None).await.map_err(request_error)?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let challenge = response
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|challenge| challenge.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let authorization = self
+                .authorize(host, &challenge, &api_repository(reference))
+                .await?;
+            response = request(Some(authorization)).await.map_err(request_error)?;
+        }
+
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Err(CommandError::DockerError(format!(
+                    "Access to '{}' on '{}' was denied; check the credentials in ~/.docker/config.json",
+                    reference.name(),
+                    host
+                )))
+            }
+            StatusCode::NOT_FOUND => Err(CommandError::DockerError(format!(
+                "'{}' was not found on '{}'",
+                reference, host
+            ))),
+            status => Err(CommandError::DockerError(format!(
+                "Registry '{}' answered {} for '{}'",
+                host, status, reference
+            ))),
+        }
+    }
+
+    /// Builds the `Authorization` header answering a `WWW-Authenticate`
+    /// challenge: basic credentials as-is, or a bearer token fetched from the
+    /// challenge's realm.
+    async fn authorize(
+        &self,
+        host: &str,
+        challenge: &str,
+        repository: &str,
+    ) -> Result<String, CommandError> {
+        let credentials = credentials(host).await;
+        let (scheme, params) = parse_challenge(challenge);
+
+        if scheme.eq_ignore_ascii_case("basic") {
+            let credentials = credentials.ok_or_else(|| {
+                CommandError::DockerError(format!(
+                    "Registry '{}' requires credentials; run `docker login {}`",
+                    host, host
+                ))
+            })?;
+            if credentials.is_identity_token() {
+                return Err(CommandError::Unsupported(format!(
+                    "Registry '{}' asks for a password, but only an identity token is stored; \
+                     run `docker login {}` with a password",
+                    host, host
+                )));
+            }
+            return Ok(basic_authorization(&credentials));
+        }
+
+        let realm = params.get("realm").ok_or_else(|| {
+            CommandError::DockerError(format!(
+                "Registry '{}' sent an unsupported authentication challenge",
+                host
+            ))
+        })?;
+        let scope = params
+            .get("scope")
+            .cloned()
+            .unwrap_or_else(|| format!("repository:{}:pull", repository));
+        let mut query = vec![("scope", scope)];
+        if let Some(service) = params.get("service") {
+            query.push(("service", service.clone()));
+        }
+
+        let request = match &credentials {
+            // Identity tokens are OAuth2 refresh tokens and are exchanged
+            // rather than sent as a password.
+            Some(credentials) if credentials.is_identity_token() => self
+                .http
+                .post(realm)
+                .form(&refresh_token_form(query, credentials)),
+            Some(credentials) => self
+                .http
+                .get(realm)
+                .query(&query)
+                .header(AUTHORIZATION, basic_authorization(credentials)),
+            None => self.http.get(realm).query(&query),
+        };
+        let token: TokenResponse = request
+            .send()
+            .await
+            .and_then(Response::error_for_status)
+            .map_err(|e| {
+                CommandError::DockerError(format!(
+                    "Failed to authenticate with registry '{}': {}",
+                    host, e
+                ))
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                CommandError::DockerError(format!(
+                    "Invalid token response from registry '{}': {}",
+                    host, e
+                ))
+            })?;
+
+        token
+            .token
+            .or(token.access_token)
+            .map(|token| format!("Bearer {}", token))
+            .ok_or_else(|| {
+                CommandError::DockerError(format!("Registry '{}' did not issue a token", host))
+            })
+    }
+}
+
+/// The registry a reference lives on, with Docker Hub spelled `docker.io`.
+pub fn registry_host(reference: &ImageReference) -> &str {
+    match reference.registry.as_deref() {
+        None | Some("index.docker.io") | Some(DOCKER_HUB_API) => DOCKER_HUB,
+        Some(registry) => registry,
+    }
+}
+
+/// The repository path as the v2 API expects it; official Docker Hub images
+/// live under `library/`.
+pub fn api_repository(reference: &ImageReference) -> String {
+    if registry_host(reference) == DOCKER_HUB && !reference.repository.contains('/') {
+        format!("library/{}", reference.repository)
+    } else {
+        reference.repository.clone()
+    }
+}
+
+fn api_host(host: &str) -> &str {
+    if host == DOCKER_HUB {
+        DOCKER_HUB_API
+    } else {
+        host
+    }
+}
+
+/// Like the daemon, talk plain HTTP to registries on the local machine.
+fn scheme(host: &str) -> &'static str {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if port.parse::<u16>().is_ok() => name,
+        _ => host,
+    };
+
+    if matches!(name, "localhost" | "127.0.0.1" | "[::1]") {
+        "http"
+    } else {
+        "https"
+    }
+}
+
+/// Splits `Bearer realm="...",service="...",scope="..."` into the scheme and
+/// its parameters.
+fn parse_challenge(challenge: &str) -> (&str, HashMap<String, String>) {
+    let (scheme, rest) = challenge
+        .trim()
+        .split_once(' ')
+        .unwrap_or((challenge.trim(), ""));
+    let mut params = HashMap::new();
+    let mut rest = rest.trim();
+
+    while let Some((key, value)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_lowercase();
+        let value = value.trim_start();
+        let (value, remaining) = match value.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => value.split_once(',').unwrap_or((value, "")),
+        };
+        params.insert(key, value.to_string());
+        rest = remaining.trim_start_matches(',').trim();
+    }
+
+    (scheme, params)
+}
+
+/// Extracts the `last` parameter from `</v2/<name>/tags/list?n=..&last=..>; rel="next"`.
+fn next_cursor(link: &str) -> Option<String> {
+    let (target, relation) = link.split_once(';')?;
+    if !relation.contains("rel=\"next\"") {
+        return None;
+    }
+    let target = target.trim().trim_start_matches('<').trim_end_matches('>');
+    let url = reqwest::Url::parse("http://registry")
+        .ok()?
+        .join(target)
+        .ok()?;
+
+    url.query_pairs()
+        .find(|(key, _)| key == "last")
+        .map(|(_, value)| value.into_owned())
+}
+
+fn refresh_token_form(
+    mut params: Vec<(&'static str, String)>,
+    credentials: &Credentials,
+) -> Vec<(&'static str, String)> {
+    params.push(("grant_type", "refresh_token".to_string()));
+    params.push(("refresh_token", credentials.secret.clone()));
+    params.push(("client_id", OAUTH_CLIENT_ID.to_string()));
+    params
+}
+
+fn basic_authorization(credentials: &Credentials) -> String {
+    format!(
+        "Basic {}",
+        BASE64_STANDARD.encode(format!("{}:{}", credentials.username, credentials.secret))
+    )
+}
+
+fn docker_config_path() -> Option<PathBuf> {
+    match std::env::var("DOCKER_CONFIG") {
+        Ok(dir) => Some(PathBuf::from(dir).join("config.json")),
+        Err(_) => std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".docker/config.json")),
+    }
+}
+
+/// Looks up credentials for `host` the way the docker CLI does: a per-host
+/// credential helper, then the global credential store, then inline `auths`.
+async fn credentials(host: &str) -> Option<Credentials> {
+    let contents = tokio::fs::read_to_string(docker_config_path()?)
+        .await
+        .ok()?;
+    let config: DockerConfig = serde_json::from_str(&contents).ok()?;
+
+    let keys: Vec<String> = if host == DOCKER_HUB {
+        [
+            DOCKER_HUB_AUTH_KEY,
+            "index.docker.io",
+            DOCKER_HUB,
+            DOCKER_HUB_API,
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    } else {
+        vec![
+            host.to_string(),
+            format!("https://{}", host),
+            format!("http://{}", host),
+        ]
+    };
+    let server = &keys[0];
+
+    let helper = keys
+        .iter()
+        .find_map(|key| config.cred_helpers.get(key))
+        .or(config.creds_store.as_ref());
+    if let Some(helper) = helper {
+        if let Some(credentials) = helper_credentials(helper, server).await {
+            return Some(credentials);
+        }
+    }
+
+    let entry = keys.iter().find_map(|key| config.auths.get(key))?;
+    if let Some(token) = &entry.identity_token {
+        return Some(Credentials {
+            username: IDENTITY_TOKEN_USERNAME.to_string(),
+            secret: token.clone(),
+        });
+    }
+    if let (Some(username), Some(password)) = (&entry.username, &entry.password) {
+        return Some(Credentials {
+            username: username.clone(),
+            secret: password.clone(),
+        });
+    }
+    let decoded = BASE64_STANDARD.decode(entry.auth.as_deref()?).ok()?;
+    let (username, secret) = String::from_utf8(decoded)
+        .ok()?
+        .split_once(':')
+        .map(|(username, secret)| (username.to_string(), secret.to_string()))?;
+
+    Some(Credentials { username, secret })
+}
+
+async fn helper_credentials(helper: &str, server: &str) -> Option<Credentials> {
+    let mut child = tokio::process::Command::new(format!("docker-credential-{}", helper))
+        .arg("get")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let mut stdin = child.stdin.take()?;
+    stdin.write_all(server.as_bytes()).await.ok()?;
+    drop(stdin);
+
+    let output = child.wait_with_output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let credentials: HelperCredentials = serde_json::from_slice(&output.stdout).ok()?;
+
+    Some(Credentials {
+        username: credentials.username,
+        secret: credentials.secret,
+    })
+}
+
+#[tauri::command]
+pub async fn search_images(
+    state: State<'_, AppState>,
+    term: &str,
+    limit: Option<u64>,
+    min_stars: Option<u64>,
+    official_only: Option<bool>,
+) -> Result<Vec<ImageSearchResult>, CommandError> {
+    let stars = min_stars.map(|stars| stars.to_string());
+    let mut filters = HashMap::new();
+    if let Some(stars) = &stars {
+        filters.insert("stars", vec![stars.as_str()]);
+    }
+    if official_only.unwrap_or(false) {
+        filters.insert("is-official", vec!["true"]);
+    }
+
+    let results = state
+        .docker
+        .search_images(SearchImagesOptions {
+            term,
+            limit,
+            filters,
+        })
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!("Failed to search images for '{}': {}", term, e))
+        })?;
+
+    Ok(results
+        .into_iter()
+        .map(|result| ImageSearchResult {
+            name: result.name,
+            description: result
+                .description
+                .filter(|description| !description.is_empty()),
+            stars: result.star_count,
+            official: result.is_official.unwrap_or(false),
+            automated: result.is_automated.unwrap_or(false),
+        })
+        .collect())
+}
+
+/// Lists the tags of a repository page by page; pass the returned `next`
+/// back as `last` to continue.
+#[tauri::command]
+pub async fn list_remote_tags(
+    state: State<'_, AppState>,
+    repository: &str,
+    page_size: Option<u32>,
+    last: Option<String>,
+) -> Result<RemoteTagPage, CommandError> {
+    let reference = ImageReference::parse(repository)?;
+    let page_size = page_size
+        .unwrap_or(DEFAULT_TAG_PAGE_SIZE)
+        .clamp(1, MAX_TAG_PAGE_SIZE);
+
+    let (tags, next) = state
+        .registry
+        .list_tags(&reference, page_size, last.as_deref())
+        .await?;
+
+    Ok(RemoteTagPage {
+        repository: reference.name(),
+        tags,
+        next,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_next_cursor() {
+        assert_eq!(
+            next_cursor(r#"</v2/library/nginx/tags/list?n=50&last=1.25.3>; rel="next""#),
+            Some("1.25.3".to_string())
+        );
+        assert_eq!(
+            next_cursor(
+                r#"<https://ghcr.io/v2/owner/app/tags/list?last=v1.0%2Bbuild&n=100>; rel="next""#
+            ),
+            Some("v1.0+build".to_string())
+        );
+    }
+
+    #[test]
+    fn ignores_links_without_a_next_cursor() {
+        assert_eq!(
+            next_cursor(r#"</v2/library/nginx/tags/list?n=50&last=1.25.3>; rel="prev""#),
+            None
+        );
+        assert_eq!(
+            next_cursor(r#"</v2/library/nginx/tags/list?n=50>; rel="next""#),
+            None
+        );
+        assert_eq!(next_cursor("</v2/library/nginx/tags/list?last=a>"), None);
+    }
+
+    #[test]
+    fn parses_bearer_challenges() {
+        let (scheme, params) = parse_challenge(
+            r#"Bearer realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:library/nginx:pull""#,
+        );
+
+        assert_eq!(scheme, "Bearer");
+        assert_eq!(params["realm"], "https://auth.docker.io/token");
+        assert_eq!(params["service"], "registry.docker.io");
+        assert_eq!(params["scope"], "repository:library/nginx:pull");
+    }
+
+    #[test]
+    fn exchanges_identity_tokens_as_refresh_tokens() {
+        let credentials = Credentials {
+            username: IDENTITY_TOKEN_USERNAME.to_string(),
+            secret: "refresh".to_string(),
+        };
+        let params = vec![
+            ("scope", "repository:team/app:pull".to_string()),
+            ("service", "registry.example.com".to_string()),
+        ];
+
+        assert!(credentials.is_identity_token());
+        assert_eq!(
+            refresh_token_form(params, &credentials),
+            [
+                ("scope", "repository:team/app:pull".to_string()),
+                ("service", "registry.example.com".to_string()),
+                ("grant_type", "refresh_token".to_string()),
+                ("refresh_token", "refresh".to_string()),
+                ("client_id", OAUTH_CLIENT_ID.to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+use bollard::models::{
+    Config, ConfigSpec, IdResponse, Secret, SecretSpec, TaskSpecContainerSpecConfigs,
+    TaskSpecContainerSpecFile, TaskSpecContainerSpecFile1, TaskSpecContainerSpecSecrets,
+};
+use bollard::secret::ListSecretsOptions;
+use tauri::State;
+
+use crate::engine::encode;
+use crate::error::CommandError;
+use crate::payload::{ConfigDetails, ConfigSummary, SecretSummary};
+use crate::swarm::{modify_service, require_manager};
+use crate::AppState;
+
+// Secret payloads only ever travel frontend -> engine. Nothing in this module
+// returns, logs or formats them into errors.
+
+#[tauri::command]
+pub async fn list_secrets(state: State<'_, AppState>) -> Result<Vec<SecretSummary>, CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+
+    let secrets = docker
+        .list_secrets(None::<ListSecretsOptions<String>>)
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to list secrets: {}", e)))?;
+
+    Ok(secrets.into_iter().map(secret_summary).collect())
+}
+
+#[tauri::command]
+pub async fn inspect_secret(
+    state: State<'_, AppState>,
+    secret_name: &str,
+) -> Result<SecretSummary, CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+
+    let secret = docker.inspect_secret(secret_name).await.map_err(|e| {
+        CommandError::DockerError(format!("Failed to inspect secret '{}': {}", secret_name, e))
+    })?;
+
+    Ok(secret_summary(secret))
+}
+
+#[tauri::command]
+pub async fn create_secret(
+    state: State<'_, AppState>,
+    name: &str,
+    data: String,
+    labels: Option<HashMap<String, String>>,
+) -> Result<String, CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+
+    let spec = SecretSpec {
+        name: Some(name.to_string()),
+        labels,
+        data: Some(BASE64_STANDARD.encode(data)),
+        ..Default::default()
+    };
+
+    let response = docker.create_secret(spec).await.map_err(|e| {
+        CommandError::DockerError(format!("Failed to create secret '{}': {}", name, e))
+    })?;
+
+    Ok(response.id)
+}
+
+#[tauri::command]
+pub async fn remove_secret(
+    state: State<'_, AppState>,
+    secret_name: &str,
+) -> Result<(), CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+
+    docker.delete_secret(secret_name).await.map_err(|e| {
+        CommandError::DockerError(format!("Failed to remove secret '{}': {}", secret_name, e))
+    })
+}
+
+#[tauri::command]
+pub async fn list_configs(state: State<'_, AppState>) -> Result<Vec<ConfigSummary>, CommandError> {
+    require_manager(&state).await?;
+
+    let configs: Vec<Config> = state.engine.get("/configs").await?;
+
+    Ok(configs.into_iter().map(config_summary).collect())
+}
+
+#[tauri::command]
+pub async fn inspect_config(
+    state: State<'_, AppState>,
+    config_name: &str,
+) -> Result<ConfigDetails, CommandError> {
+    require_manager(&state).await?;
+
+    let config: Config = state
+        .engine
+        .get(&format!("/configs/{}", encode(config_name)))
+        .await?;
+    let data = config
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.data.as_deref())
+        .map(|data| BASE64_STANDARD.decode(data))
+        .transpose()
+        .map_err(|e| {
+            CommandError::UnexpectedError(format!(
+                "Failed to decode config '{}': {}",
+                config_name, e
+            ))
+        })?
+        .unwrap_or_default();
+
+    Ok(ConfigDetails {
+        summary: config_summary(config),
+        data: String::from_utf8_lossy(&data).into_owned(),
+    })
+}
+
+#[tauri::command]
+pub async fn create_config(
+    state: State<'_, AppState>,
+    name: &str,
+    data: String,
+    labels: Option<HashMap<String, String>>,
+) -> Result<String, CommandError> {
+    require_manager(&state).await?;
+
+    let spec = ConfigSpec {
+        name: Some(name.to_string()),
+        labels,
+        data: Some(BASE64_STANDARD.encode(data)),
+        ..Default::default()
+    };
+
+    let response: IdResponse = state
+        .engine
+        .post("/configs/create", &spec)
+        .await
+        .map_err(|e| match e {
+            CommandError::DockerError(message) => CommandError::DockerError(format!(
+                "Failed to create config '{}': {}",
+                name, message
+            )),
+            e => e,
+        })?;
+
+    Ok(response.id)
+}
+
+#[tauri::command]
+pub async fn remove_config(
+    state: State<'_, AppState>,
+    config_name: &str,
+) -> Result<(), CommandError> {
+    require_manager(&state).await?;
+
+    state
+        .engine
+        .delete(&format!("/configs/{}", encode(config_name)))
+        .await
+}
+
+#[tauri::command]
+pub async fn attach_secret_to_service(
+    state: State<'_, AppState>,
+    service_name: &str,
+    secret_name: &str,
+    target: Option<String>,
+) -> Result<(), CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+    let secret = docker.inspect_secret(secret_name).await.map_err(|e| {
+        CommandError::DockerError(format!("Failed to inspect secret '{}': {}", secret_name, e))
+    })?;
+    let secret_id = secret.id.unwrap_or_default();
+
+    modify_service(&state, service_name, |spec| {
+        let container = spec
+            .task_template
+            .get_or_insert_with(Default::default)
+            .container_spec
+            .get_or_insert_with(Default::default);
+        let secrets = container.secrets.get_or_insert_with(Vec::new);
+        secrets.retain(|attached| attached.secret_id.as_deref() != Some(secret_id.as_str()));
+        secrets.push(TaskSpecContainerSpecSecrets {
+            file: Some(TaskSpecContainerSpecFile {
+                name: Some(target.unwrap_or_else(|| secret_name.to_string())),
+                uid: Some("0".to_string()),
+                gid: Some("0".to_string()),
+                mode: Some(0o444),
+            }),
+            secret_id: Some(secret_id.clone()),
+            secret_name: Some(secret_name.to_string()),
+        });
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn detach_secret_from_service(
+    state: State<'_, AppState>,
+    service_name: &str,
+    secret_name: &str,
+) -> Result<(), CommandError> {
+    modify_service(&state, service_name, |spec| {
+        if let Some(secrets) = spec
+            .task_template
+            .as_mut()
+            .and_then(|template| template.container_spec.as_mut())
+            .and_then(|container| container.secrets.as_mut())
+        {
+            secrets.retain(|attached| attached.secret_name.as_deref() != Some(secret_name));
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn attach_config_to_service(
+    state: State<'_, AppState>,
+    service_name: &str,
+    config_name: &str,
+    target: Option<String>,
+) -> Result<(), CommandError> {
+    let config: Config = state
+        .engine
+        .get(&format!("/configs/{}", encode(config_name)))
+        .await?;
+    let config_id = config.id.unwrap_or_default();
+
+    modify_service(&state, service_name, |spec| {
+        let container = spec
+            .task_template
+            .get_or_insert_with(Default::default)
+            .container_spec
+            .get_or_insert_with(Default::default);
+        let configs = container.configs.get_or_insert_with(Vec::new);
+        configs.retain(|attached| attached.config_id.as_deref() != Some(config_id.as_str()));
+        configs.push(TaskSpecContainerSpecConfigs {
+            file: Some(TaskSpecContainerSpecFile1 {
+                name: Some(target.unwrap_or_else(|| format!("/{}", config_name))),
+                uid: Some("0".to_string()),
+                gid: Some("0".to_string()),
+                mode: Some(0o444),
+            }),
+            config_id: Some(config_id.clone()),
+            config_name: Some(config_name.to_string()),
+            ..Default::default()
+        });
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn detach_config_from_service(
+    state: State<'_, AppState>,
+    service_name: &str,
+    config_name: &str,
+) -> Result<(), CommandError> {
+    modify_service(&state, service_name, |spec| {
+        if let Some(configs) = spec
+            .task_template
+            .as_mut()
+            .and_then(|template| template.container_spec.as_mut())
+            .and_then(|container| container.configs.as_mut())
+        {
+            configs.retain(|attached| attached.config_name.as_deref() != Some(config_name));
+        }
+        Ok(())
+    })
+    .await
+}
+
+fn secret_summary(secret: Secret) -> SecretSummary {
+    let spec = secret.spec.unwrap_or_default();
+
+    SecretSummary {
+        id: secret.id.unwrap_or_default(),
+        name: spec.name,
+        labels: spec.labels.unwrap_or_default(),
+        driver: spec.driver.map(|driver| driver.name),
+        created_at: secret.created_at,
+        updated_at: secret.updated_at,
+    }
+}
+
+fn config_summary(config: Config) -> ConfigSummary {
+    let spec = config.spec.unwrap_or_default();
+
+    ConfigSummary {
+        id: config.id.unwrap_or_default(),
+        name: spec.name,
+        labels: spec.labels.unwrap_or_default(),
+        created_at: config.created_at,
+        updated_at: config.updated_at,
+    }
+}
@@ -0,0 +1,312 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::{Emitter, State};
+
+use crate::engine::Endpoint;
+use crate::error::CommandError;
+use crate::payload::{Settings, SettingsPatch};
+use crate::AppState;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+const SETTINGS_VERSION: u32 = 1;
+const MAX_STOP_TIMEOUT: i64 = 600;
+const MAX_REFRESH_INTERVAL: u64 = 3600;
+
+/// Upgrades a stored document one version at a time: entry `n` takes a
+/// version `n` document to version `n + 1`.
+const MIGRATIONS: [fn(&mut Map<String, Value>); SETTINGS_VERSION as usize] = [migrate_v0];
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            stop_timeout_seconds: 10,
+            log_tail: "all".to_string(),
+            refresh_interval_seconds: 5,
+            default_network_driver: "bridge".to_string(),
+            docker_host: None,
+        }
+    }
+}
+
+/// Files written before settings were versioned carry no `version` field;
+/// their keys already match version 1 and missing ones fall back to defaults.
+fn migrate_v0(_settings: &mut Map<String, Value>) {}
+
+/// App settings persisted as JSON in the app config dir. Until `load` runs,
+/// and whenever the file is missing, the defaults apply.
+#[derive(Default)]
+pub struct SettingsStore {
+    path: Mutex<Option<PathBuf>>,
+    current: Mutex<Settings>,
+}
+
+impl SettingsStore {
+    /// Never fails: an unreadable or invalid file is logged and replaced by
+    /// the defaults so a bad settings file cannot keep the app from starting.
+    pub fn load(&self, path: PathBuf) {
+        let settings = match std::fs::read_to_string(&path) {
+            Ok(contents) => match parse(&contents) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    println!("Ignoring settings file '{}': {}", path.display(), e);
+                    // Keep the rejected file around instead of silently losing
+                    // it on the next save.
+                    let _ = std::fs::rename(&path, path.with_extension("json.bak"));
+                    Settings::default()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Settings::default(),
+            Err(e) => {
+                println!("Failed to read settings from '{}': {}", path.display(), e);
+                Settings::default()
+            }
+        };
+
+        if let Ok(mut current) = self.current.lock() {
+            *current = settings;
+        }
+        if let Ok(mut current_path) = self.path.lock() {
+            *current_path = Some(path);
+        }
+    }
+
+    pub fn get(&self) -> Settings {
+        self.current
+            .lock()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Applies the fields set in `patch` on top of the current settings.
+    pub fn update(&self, patch: SettingsPatch) -> Result<Settings, CommandError> {
+        let mut current = self.current.lock().map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to save settings: {}", e))
+        })?;
+
+        let mut settings = current.clone();
+        if let Some(stop_timeout_seconds) = patch.stop_timeout_seconds {
+            settings.stop_timeout_seconds = stop_timeout_seconds;
+        }
+        if let Some(log_tail) = patch.log_tail {
+            settings.log_tail = log_tail;
+        }
+        if let Some(refresh_interval_seconds) = patch.refresh_interval_seconds {
+            settings.refresh_interval_seconds = refresh_interval_seconds;
+        }
+        if let Some(default_network_driver) = patch.default_network_driver {
+            settings.default_network_driver = default_network_driver;
+        }
+        if let Some(docker_host) = patch.docker_host {
+            settings.docker_host = Some(docker_host).filter(|host| !host.is_empty());
+        }
+        validate(&settings)?;
+        settings.version = SETTINGS_VERSION;
+
+        let path = self
+            .path
+            .lock()
+            .map_err(|e| CommandError::UnexpectedError(format!("Failed to save settings: {}", e)))?
+            .clone()
+            .ok_or_else(|| {
+                CommandError::UnexpectedError("Settings have not been loaded yet".to_string())
+            })?;
+        write_json(&path, &settings)?;
+        *current = settings.clone();
+
+        Ok(settings)
+    }
+}
+
+fn parse(contents: &str) -> Result<Settings, CommandError> {
+    let mut document: Map<String, Value> = serde_json::from_str(contents)
+        .map_err(|e| CommandError::UnexpectedError(format!("Invalid settings file: {}", e)))?;
+    let version = document.get("version").and_then(Value::as_u64).unwrap_or(0) as usize;
+
+    // Documents from a newer app are read as-is; unknown keys are ignored.
+    for migration in MIGRATIONS.iter().skip(version) {
+        migration(&mut document);
+    }
+    document.insert("version".to_string(), SETTINGS_VERSION.into());
+
+    let settings = serde_json::from_value(Value::Object(document))
+        .map_err(|e| CommandError::UnexpectedError(format!("Invalid settings file: {}", e)))?;
+    validate(&settings)?;
+
+    Ok(settings)
+}
+
+/// Writes `value` as pretty JSON next to `path` and renames it into place, so
+/// a crash never leaves a half-written file behind.
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), CommandError> {
+    let dir = path.parent().ok_or_else(|| {
+        CommandError::UnexpectedError(format!("Invalid path '{}'", path.display()))
+    })?;
+    std::fs::create_dir_all(dir).map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to create '{}': {}", dir.display(), e))
+    })?;
+
+    let contents = serde_json::to_vec_pretty(value).map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to encode '{}': {}", path.display(), e))
+    })?;
+
+    let write_error = |e: std::io::Error| {
+        CommandError::UnexpectedError(format!("Failed to write '{}': {}", path.display(), e))
+    };
+    let mut file = tempfile::NamedTempFile::new_in(dir).map_err(write_error)?;
+    std::io::Write::write_all(&mut file, &contents).map_err(write_error)?;
+    file.persist(path).map_err(|e| write_error(e.error))?;
+
+    Ok(())
+}
+
+fn validate(settings: &Settings) -> Result<(), CommandError> {
+    if !(0..=MAX_STOP_TIMEOUT).contains(&settings.stop_timeout_seconds) {
+        return Err(CommandError::UnexpectedError(format!(
+            "Stop timeout must be between 0 and {} seconds",
+            MAX_STOP_TIMEOUT
+        )));
+    }
+
+    if settings.log_tail != "all" && settings.log_tail.parse::<u64>().is_err() {
+        return Err(CommandError::UnexpectedError(format!(
+            "Invalid log tail '{}': expected 'all' or a number of lines",
+            settings.log_tail
+        )));
+    }
+
+    if !(1..=MAX_REFRESH_INTERVAL).contains(&settings.refresh_interval_seconds) {
+        return Err(CommandError::UnexpectedError(format!(
+            "Refresh interval must be between 1 and {} seconds",
+            MAX_REFRESH_INTERVAL
+        )));
+    }
+
+    let driver = &settings.default_network_driver;
+    if driver.is_empty() || driver.chars().any(char::is_whitespace) {
+        return Err(CommandError::UnexpectedError(format!(
+            "Invalid network driver '{}'",
+            driver
+        )));
+    }
+
+    if let Some(host) = &settings.docker_host {
+        validate_docker_host(host)?;
+    }
+
+    Ok(())
+}
+
+fn validate_docker_host(host: &str) -> Result<(), CommandError> {
+    #[cfg(unix)]
+    if host.starts_with("ssh://") {
+        return crate::ssh::SshDestination::parse(host)
+            .map(|_| ())
+            .map_err(CommandError::UnexpectedError);
+    }
+
+    match Endpoint::parse(host) {
+        Some(_) => Ok(()),
+        None => Err(CommandError::UnexpectedError(format!(
+            "Unsupported Docker host '{}'",
+            host
+        ))),
+    }
+}
+
+#[tauri::command]
+pub async fn get_settings(state: State<'_, AppState>) -> Result<Settings, CommandError> {
+    Ok(state.settings.get())
+}
+
+#[tauri::command]
+pub async fn update_settings(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: SettingsPatch,
+) -> Result<Settings, CommandError> {
+    let settings = state.settings.update(settings)?;
+
+    app_handle
+        .emit("settings-changed", settings.clone())
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to emit settings : {}", e)))?;
+
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgrades_unversioned_files() {
+        let settings = parse(r#"{"stop_timeout_seconds": 30, "log_tail": "100"}"#).unwrap();
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.stop_timeout_seconds, 30);
+        assert_eq!(settings.log_tail, "100");
+        assert_eq!(
+            settings.refresh_interval_seconds,
+            Settings::default().refresh_interval_seconds
+        );
+    }
+
+    #[test]
+    fn reads_files_from_newer_versions() {
+        let settings =
+            parse(r#"{"version": 99, "refresh_interval_seconds": 20, "theme": "dark"}"#).unwrap();
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.refresh_interval_seconds, 20);
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(parse("not json").is_err());
+        assert!(parse(r#"["settings"]"#).is_err());
+        assert!(parse(r#"{"version": 1, "stop_timeout_seconds": "ten"}"#).is_err());
+        assert!(parse(r#"{"version": 1, "refresh_interval_seconds": 0}"#).is_err());
+    }
+
+    #[test]
+    fn invalid_files_fall_back_to_defaults_with_a_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        std::fs::write(&path, r#"{"log_tail": "everything"}"#).unwrap();
+
+        let store = SettingsStore::default();
+        store.load(path.clone());
+
+        assert_eq!(store.get().log_tail, Settings::default().log_tail);
+        assert!(!path.exists());
+        assert!(path.with_extension("json.bak").exists());
+    }
+
+    #[test]
+    fn partial_updates_keep_other_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        std::fs::write(&path, r#"{"version": 1, "stop_timeout_seconds": 30}"#).unwrap();
+
+        let store = SettingsStore::default();
+        store.load(path.clone());
+        let settings = store
+            .update(SettingsPatch {
+                log_tail: Some("500".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(settings.stop_timeout_seconds, 30);
+        assert_eq!(settings.log_tail, "500");
+        assert_eq!(
+            parse(&std::fs::read_to_string(&path).unwrap())
+                .unwrap()
+                .log_tail,
+            "500"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use openssh::{ForwardType, KnownHosts, Session, SessionBuilder, Socket};
+use tauri::{AppHandle, Emitter};
+
+use crate::payload::{SshTunnelStatus, TunnelState};
+
+const REMOTE_SOCKET: &str = "/var/run/docker.sock";
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(2);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A parsed `ssh://[user@]host[:port]` destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshDestination {
+    pub user: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+}
+
+impl SshDestination {
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let invalid = |reason: &str| format!("Invalid ssh host '{}': {}", url, reason);
+        let rest = url
+            .strip_prefix("ssh://")
+            .ok_or_else(|| invalid("expected ssh://[user@]host[:port]"))?;
+        let authority = rest.strip_suffix('/').unwrap_or(rest);
+        if authority.contains('/') {
+            return Err(invalid("paths are not supported"));
+        }
+
+        let (user, host_port) = match authority.rsplit_once('@') {
+            Some(("", _)) => return Err(invalid("empty user")),
+            Some((user, host_port)) => (Some(user.to_string()), host_port),
+            None => (None, authority),
+        };
+
+        // Bracketed IPv6 literals carry colons of their own.
+        let (host, port) = if let Some(bracketed) = host_port.strip_prefix('[') {
+            let (host, after) = bracketed
+                .split_once(']')
+                .ok_or_else(|| invalid("unterminated '['"))?;
+            match after {
+                "" => (host, None),
+                _ => (
+                    host,
+                    Some(after.strip_prefix(':').ok_or_else(|| invalid("bad port"))?),
+                ),
+            }
+        } else {
+            match host_port.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (host_port, None),
+            }
+        };
+
+        if host.is_empty() {
+            return Err(invalid("missing host"));
+        }
+        let port = port
+            .map(|port| port.parse::<u16>().map_err(|_| invalid("bad port")))
+            .transpose()?;
+
+        Ok(SshDestination {
+            user,
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+impl fmt::Display for SshDestination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ssh://")?;
+        if let Some(user) = &self.user {
+            write!(f, "{}@", user)?;
+        }
+        if self.host.contains(':') {
+            write!(f, "[{}]", self.host)?;
+        } else {
+            write!(f, "{}", self.host)?;
+        }
+        if let Some(port) = self.port {
+            write!(f, ":{}", port)?;
+        }
+        Ok(())
+    }
+}
+
+/// Doubles the reconnect delay up to `MAX_RECONNECT_DELAY`.
+fn next_delay(delay: Duration) -> Duration {
+    (delay * 2).min(MAX_RECONNECT_DELAY)
+}
+
+/// Path of the local end of the tunnel for this process.
+pub fn local_socket_path() -> PathBuf {
+    std::env::temp_dir().join(format!("docker-vue-{}.sock", std::process::id()))
+}
+
+/// Removes the local end of the tunnel, if any; called when the app exits.
+pub fn remove_local_socket() {
+    let _ = std::fs::remove_file(local_socket_path());
+}
+
+/// Forwards the Docker socket of an `ssh://user@host[:port]` destination to a
+/// local unix socket, so bollard and the engine client can use it as if the
+/// daemon were local. Authentication is left to the system `ssh` client,
+/// which uses the user's agent, key files and `~/.ssh/config`.
+pub struct SshTunnel {
+    destination: String,
+    local_socket: PathBuf,
+}
+
+impl SshTunnel {
+    /// Returns a tunnel for `host` when it is an `ssh://` destination.
+    pub fn from_host(host: &str) -> Option<Result<Self, String>> {
+        if !host.starts_with("ssh://") {
+            return None;
+        }
+
+        Some(SshDestination::parse(host).map(|destination| SshTunnel {
+            destination: destination.to_string(),
+            local_socket: local_socket_path(),
+        }))
+    }
+
+    pub fn local_socket(&self) -> &Path {
+        &self.local_socket
+    }
+
+    /// Opens the tunnel and keeps it up for the lifetime of the app. Resolves
+    /// once the first connection is up (or `STARTUP_TIMEOUT` has passed), so
+    /// callers can talk to the daemon right after; later reconnects happen in
+    /// the background with backoff.
+    pub async fn start(self, app_handle: AppHandle) {
+        // Clear a socket left behind by a crashed run that reused our pid.
+        let _ = std::fs::remove_file(&self.local_socket);
+
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        let mut delay = MIN_RECONNECT_DELAY;
+        let mut session = self.open(&app_handle).await;
+        while session.is_none() && Instant::now() + delay < deadline {
+            tokio::time::sleep(delay).await;
+            delay = next_delay(delay);
+            session = self.open(&app_handle).await;
+        }
+
+        tauri::async_runtime::spawn(self.supervise(app_handle, session));
+    }
+
+    async fn supervise(self, app_handle: AppHandle, mut session: Option<Session>) {
+        let mut delay = MIN_RECONNECT_DELAY;
+
+        loop {
+            match &session {
+                Some(active) => {
+                    tokio::time::sleep(KEEPALIVE_INTERVAL).await;
+                    if let Err(e) = active.check().await {
+                        self.emit(&app_handle, TunnelState::Disconnected, Some(e.to_string()));
+                        session = None;
+                    }
+                }
+                None => {
+                    tokio::time::sleep(delay).await;
+                    session = self.open(&app_handle).await;
+                    delay = if session.is_some() {
+                        MIN_RECONNECT_DELAY
+                    } else {
+                        next_delay(delay)
+                    };
+                }
+            }
+        }
+    }
+
+    async fn open(&self, app_handle: &AppHandle) -> Option<Session> {
+        self.emit(app_handle, TunnelState::Connecting, None);
+
+        match self.connect().await {
+            Ok(session) => {
+                self.emit(app_handle, TunnelState::Connected, None);
+                Some(session)
+            }
+            Err(e) => {
+                self.emit(app_handle, TunnelState::Disconnected, Some(e));
+                None
+            }
+        }
+    }
+
+    async fn connect(&self) -> Result<Session, String> {
+        let session = SessionBuilder::default()
+            .known_hosts_check(KnownHosts::Strict)
+            .server_alive_interval(KEEPALIVE_INTERVAL)
+            .connect(&self.destination)
+            .await
+            .map_err(|e| format!("Failed to connect to '{}': {}", self.destination, e))?;
+
+        // A dropped master leaves its listening socket behind, which would
+        // make the new forward fail with "address already in use".
+        let _ = std::fs::remove_file(&self.local_socket);
+
+        session
+            .request_port_forward(
+                ForwardType::Local,
+                Socket::UnixSocket {
+                    path: self.local_socket.as_path().into(),
+                },
+                Socket::UnixSocket {
+                    path: Path::new(REMOTE_SOCKET).into(),
+                },
+            )
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to forward the Docker socket of '{}': {}",
+                    self.destination, e
+                )
+            })?;
+
+        Ok(session)
+    }
+
+    fn emit(&self, app_handle: &AppHandle, state: TunnelState, error: Option<String>) {
+        let _ = app_handle.emit(
+            "ssh-tunnel",
+            SshTunnelStatus {
+                destination: self.destination.clone(),
+                state,
+                error,
+            },
+        );
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.local_socket);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_destinations() {
+        assert_eq!(
+            SshDestination::parse("ssh://deploy@build-01:2222"),
+            Ok(SshDestination {
+                user: Some("deploy".to_string()),
+                host: "build-01".to_string(),
+                port: Some(2222),
+            })
+        );
+        assert_eq!(
+            SshDestination::parse("ssh://build-01/"),
+            Ok(SshDestination {
+                user: None,
+                host: "build-01".to_string(),
+                port: None,
+            })
+        );
+        assert_eq!(
+            SshDestination::parse("ssh://root@[fd00::1]:22"),
+            Ok(SshDestination {
+                user: Some("root".to_string()),
+                host: "fd00::1".to_string(),
+                port: Some(22),
+            })
+        );
+    }
+
+    #[test]
+    fn displays_parsed_destinations() {
+        for url in [
+            "ssh://build-01",
+            "ssh://deploy@build-01:2222",
+            "ssh://root@[fd00::1]:22",
+        ] {
+            assert_eq!(SshDestination::parse(url).unwrap().to_string(), url);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_destinations() {
+        for url in [
+            "tcp://build-01:2375",
+            "ssh://",
+            "ssh://deploy@",
+            "ssh://@build-01",
+            "ssh://build-01:ssh",
+            "ssh://build-01:70000",
+            "ssh://build-01/var/run/docker.sock",
+            "ssh://[fd00::1",
+            "ssh://[fd00::1]22",
+        ] {
+            assert!(SshDestination::parse(url).is_err(), "{} was accepted", url);
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let mut delay = MIN_RECONNECT_DELAY;
+        let mut delays = Vec::new();
+        for _ in 0..7 {
+            delays.push(delay.as_secs());
+            delay = next_delay(delay);
+        }
+
+        assert_eq!(delays, [2, 4, 8, 16, 32, 60, 60]);
+    }
+}
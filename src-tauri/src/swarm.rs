@@ -0,0 +1,692 @@
+use std::collections::HashMap;
+
+use bollard::models::{
+    EndpointPortConfig, EndpointPortConfigProtocolEnum, EndpointSpec, LocalNodeState, Node,
+    ServiceSpec, ServiceSpecMode, ServiceSpecModeReplicated, SwarmInitRequest, Task, TaskSpec,
+    TaskSpecContainerSpec,
+};
+use bollard::service::{ListServicesOptions, UpdateServiceOptions};
+use http_body_util::BodyExt;
+use tauri::ipc::Channel;
+use tauri::State;
+
+use crate::compat::require_feature;
+use crate::engine::{encode, EngineClient};
+use crate::error::CommandError;
+use crate::payload::{
+    ContainerOutput, Feature, NodeSummary, ServiceDefinition, ServiceSummary, SwarmStatus,
+    TaskSummary,
+};
+use crate::AppState;
+
+/// Swarm-scoped endpoints answer 503 on a worker or a standalone engine, so
+/// check up front and give the frontend something it can act on.
+pub(crate) async fn require_manager(state: &AppState) -> Result<(), CommandError> {
+    require_feature(state, Feature::Swarm).await?;
+
+    let swarm = state
+        .docker
+        .info()
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to read swarm state: {}", e)))?
+        .swarm
+        .unwrap_or_default();
+
+    if swarm.control_available.unwrap_or(false) {
+        Ok(())
+    } else {
+        Err(CommandError::Unsupported(format!(
+            "This Docker daemon is not a swarm manager (node state: {})",
+            swarm
+                .local_node_state
+                .map(|state| state.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        )))
+    }
+}
+
+#[tauri::command]
+pub async fn swarm_status(state: State<'_, AppState>) -> Result<SwarmStatus, CommandError> {
+    let swarm = state
+        .docker
+        .info()
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to read swarm state: {}", e)))?
+        .swarm
+        .unwrap_or_default();
+
+    Ok(SwarmStatus {
+        local_node_state: swarm
+            .local_node_state
+            .unwrap_or(LocalNodeState::INACTIVE)
+            .to_string(),
+        is_manager: swarm.control_available.unwrap_or(false),
+        node_id: swarm.node_id.filter(|id| !id.is_empty()),
+        nodes: swarm.nodes,
+        managers: swarm.managers,
+        error: swarm.error.filter(|error| !error.is_empty()),
+    })
+}
+
+#[tauri::command]
+pub async fn init_swarm(
+    state: State<'_, AppState>,
+    advertise_addr: Option<String>,
+    listen_addr: Option<String>,
+) -> Result<String, CommandError> {
+    require_feature(&state, Feature::Swarm).await?;
+
+    let request = SwarmInitRequest {
+        listen_addr: Some(listen_addr.unwrap_or_else(|| "0.0.0.0:2377".to_string())),
+        advertise_addr: advertise_addr.filter(|addr| !addr.is_empty()),
+        ..Default::default()
+    };
+
+    state
+        .engine
+        .post::<_, String>("/swarm/init", &request)
+        .await
+        .map_err(|e| match e {
+            CommandError::DockerError(message) => {
+                CommandError::DockerError(format!("Failed to initialize swarm: {}", message))
+            }
+            e => e,
+        })
+}
+
+#[tauri::command]
+pub async fn leave_swarm(
+    state: State<'_, AppState>,
+    force: Option<bool>,
+) -> Result<(), CommandError> {
+    require_feature(&state, Feature::Swarm).await?;
+
+    state
+        .engine
+        .post_empty(&format!("/swarm/leave?force={}", force.unwrap_or(false)))
+        .await
+}
+
+#[tauri::command]
+pub async fn list_services(
+    state: State<'_, AppState>,
+) -> Result<Vec<ServiceSummary>, CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+
+    let services = docker
+        .list_services(Some(ListServicesOptions::<String> {
+            status: true,
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to list services: {}", e)))?;
+
+    let result = services
+        .into_iter()
+        .map(|service| {
+            let spec = service.spec.unwrap_or_default();
+            let mode = spec.mode.unwrap_or_default();
+            let status = service.service_status.unwrap_or_default();
+
+            ServiceSummary {
+                id: service.id.unwrap_or_default(),
+                name: spec.name,
+                image: spec
+                    .task_template
+                    .and_then(|template| template.container_spec)
+                    .and_then(|container| container.image),
+                mode: if mode.global.is_some() {
+                    "global".to_string()
+                } else {
+                    "replicated".to_string()
+                },
+                replicas: mode.replicated.and_then(|replicated| replicated.replicas),
+                running_tasks: status.running_tasks,
+                desired_tasks: status.desired_tasks,
+                ports: spec
+                    .endpoint_spec
+                    .and_then(|endpoint| endpoint.ports)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|port| {
+                        format!(
+                            "{}:{}/{}",
+                            port.published_port.unwrap_or_default(),
+                            port.target_port.unwrap_or_default(),
+                            port.protocol
+                                .map(|protocol| protocol.to_string())
+                                .unwrap_or_else(|| "tcp".to_string())
+                        )
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn create_service(
+    state: State<'_, AppState>,
+    definition: ServiceDefinition,
+) -> Result<String, CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+
+    let ports = definition
+        .ports
+        .unwrap_or_default()
+        .iter()
+        .map(|mapping| parse_service_port(mapping))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mode = if definition.global.unwrap_or(false) {
+        ServiceSpecMode {
+            global: Some(HashMap::new()),
+            ..Default::default()
+        }
+    } else {
+        ServiceSpecMode {
+            replicated: Some(ServiceSpecModeReplicated {
+                replicas: Some(definition.replicas.unwrap_or(1)),
+            }),
+            ..Default::default()
+        }
+    };
+
+    let spec = ServiceSpec {
+        name: Some(definition.name.clone()),
+        task_template: Some(TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some(definition.image),
+                args: definition.command,
+                env: definition.env,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        mode: Some(mode),
+        endpoint_spec: (!ports.is_empty()).then(|| EndpointSpec {
+            ports: Some(ports),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let response = docker.create_service(spec, None).await.map_err(|e| {
+        CommandError::DockerError(format!(
+            "Failed to create service '{}': {}",
+            definition.name, e
+        ))
+    })?;
+
+    Ok(response.id.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn update_service(
+    state: State<'_, AppState>,
+    service_name: &str,
+    image: Option<String>,
+    env: Option<Vec<String>>,
+    replicas: Option<i64>,
+) -> Result<(), CommandError> {
+    update_service_inner(&state, service_name, image, env, replicas).await
+}
+
+#[tauri::command]
+pub async fn scale_service(
+    state: State<'_, AppState>,
+    service_name: &str,
+    replicas: i64,
+) -> Result<(), CommandError> {
+    update_service_inner(&state, service_name, None, None, Some(replicas)).await
+}
+
+async fn update_service_inner(
+    state: &AppState,
+    service_name: &str,
+    image: Option<String>,
+    env: Option<Vec<String>>,
+    replicas: Option<i64>,
+) -> Result<(), CommandError> {
+    modify_service(state, service_name, |spec| {
+        if image.is_some() || env.is_some() {
+            let container = spec
+                .task_template
+                .get_or_insert_with(Default::default)
+                .container_spec
+                .get_or_insert_with(Default::default);
+            if let Some(image) = image {
+                container.image = Some(image);
+            }
+            if let Some(env) = env {
+                container.env = Some(env);
+            }
+        }
+
+        if let Some(replicas) = replicas {
+            let replicated = spec
+                .mode
+                .as_mut()
+                .and_then(|mode| mode.replicated.as_mut())
+                .ok_or_else(|| {
+                    CommandError::UnexpectedError(format!(
+                        "Service '{}' is not replicated and cannot be scaled",
+                        service_name
+                    ))
+                })?;
+            replicated.replicas = Some(replicas);
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+/// Applies `modify` to the current spec of a service and submits it against
+/// the version it was read at, so concurrent edits are rejected by the engine.
+pub(crate) async fn modify_service<F>(
+    state: &AppState,
+    service_name: &str,
+    modify: F,
+) -> Result<(), CommandError>
+where
+    F: FnOnce(&mut ServiceSpec) -> Result<(), CommandError>,
+{
+    require_manager(state).await?;
+    let docker = &state.docker;
+
+    let service = docker
+        .inspect_service(service_name, None)
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to inspect service '{}': {}",
+                service_name, e
+            ))
+        })?;
+    let version = service
+        .version
+        .and_then(|version| version.index)
+        .ok_or_else(|| {
+            CommandError::UnexpectedError(format!("Service '{}' has no version", service_name))
+        })?;
+    let mut spec = service.spec.unwrap_or_default();
+
+    modify(&mut spec)?;
+
+    docker
+        .update_service(
+            service_name,
+            spec,
+            UpdateServiceOptions {
+                version,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to update service '{}': {}",
+                service_name, e
+            ))
+        })?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_service(
+    state: State<'_, AppState>,
+    service_name: &str,
+) -> Result<(), CommandError> {
+    require_manager(&state).await?;
+    let docker = &state.docker;
+
+    docker.delete_service(service_name).await.map_err(|e| {
+        CommandError::DockerError(format!(
+            "Failed to remove service '{}': {}",
+            service_name, e
+        ))
+    })
+}
+
+#[tauri::command]
+pub async fn list_service_tasks(
+    state: State<'_, AppState>,
+    service_name: &str,
+) -> Result<Vec<TaskSummary>, CommandError> {
+    require_manager(&state).await?;
+
+    let filters = serde_json::json!({ "service": [service_name] }).to_string();
+    let tasks: Vec<Task> = state
+        .engine
+        .get(&format!("/tasks?filters={}", encode(&filters)))
+        .await?;
+
+    let mut result: Vec<TaskSummary> = tasks
+        .into_iter()
+        .map(|task| {
+            let status = task.status.unwrap_or_default();
+            TaskSummary {
+                id: task.id.unwrap_or_default(),
+                slot: task.slot,
+                node_id: task.node_id,
+                state: status.state.map(|state| state.to_string()),
+                desired_state: task.desired_state.map(|state| state.to_string()),
+                message: status.message,
+                error: status.err,
+                container_id: status
+                    .container_status
+                    .and_then(|container| container.container_id),
+                updated_at: task.updated_at,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| a.slot.cmp(&b.slot).then(b.updated_at.cmp(&a.updated_at)));
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn list_nodes(state: State<'_, AppState>) -> Result<Vec<NodeSummary>, CommandError> {
+    require_manager(&state).await?;
+
+    let nodes: Vec<Node> = state.engine.get("/nodes").await?;
+
+    let result = nodes
+        .into_iter()
+        .map(|node| {
+            let spec = node.spec.unwrap_or_default();
+            let description = node.description.unwrap_or_default();
+            let status = node.status.unwrap_or_default();
+
+            NodeSummary {
+                id: node.id.unwrap_or_default(),
+                hostname: description.hostname,
+                role: spec.role.map(|role| role.to_string()),
+                availability: spec
+                    .availability
+                    .map(|availability| availability.to_string()),
+                state: status.state.map(|state| state.to_string()),
+                address: status.addr,
+                leader: node
+                    .manager_status
+                    .and_then(|manager| manager.leader)
+                    .unwrap_or(false),
+                engine_version: description.engine.and_then(|engine| engine.engine_version),
+            }
+        })
+        .collect();
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn service_logs(
+    state: State<'_, AppState>,
+    service_name: &str,
+    tail: Option<String>,
+    follow: Option<bool>,
+    on_log: Channel<ContainerOutput>,
+    operation_id: Option<String>,
+) -> Result<(), CommandError> {
+    require_manager(&state).await?;
+
+    state
+        .operations
+        .run(
+            operation_id,
+            "service-logs",
+            service_name,
+            stream_service_logs(
+                &state.engine,
+                service_name,
+                tail.unwrap_or_else(|| state.settings.get().log_tail),
+                follow.unwrap_or(false),
+                on_log,
+            ),
+        )
+        .await
+}
+
+async fn stream_service_logs(
+    engine: &EngineClient,
+    service_name: &str,
+    tail: String,
+    follow: bool,
+    on_log: Channel<ContainerOutput>,
+) -> Result<(), CommandError> {
+    let mut body = engine
+        .stream(&format!(
+            "/services/{}/logs?stdout=true&stderr=true&follow={}&tail={}",
+            encode(service_name),
+            follow,
+            encode(&tail)
+        ))
+        .await?;
+    let mut demuxer = LogDemuxer::default();
+
+    while let Some(frame) = body.frame().await {
+        let frame = frame.map_err(|e| {
+            CommandError::DockerError(format!("Failed to read service logs: {}", e))
+        })?;
+        let Ok(data) = frame.into_data() else {
+            continue;
+        };
+
+        for output in demuxer.push(&data) {
+            on_log.send(output).map_err(|e| {
+                CommandError::UnexpectedError(format!("Failed to emit log : {}", e))
+            })?;
+        }
+    }
+
+    if let Some(output) = demuxer.flush() {
+        on_log
+            .send(output)
+            .map_err(|e| CommandError::UnexpectedError(format!("Failed to emit log : {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// Splits the engine's multiplexed log stream: every frame starts with an
+/// 8-byte header holding the stream type and the big-endian payload length.
+#[derive(Default)]
+struct LogDemuxer {
+    buffer: Vec<u8>,
+}
+
+impl LogDemuxer {
+    fn push(&mut self, data: &[u8]) -> Vec<ContainerOutput> {
+        self.buffer.extend_from_slice(data);
+        let mut outputs = Vec::new();
+
+        loop {
+            if self.buffer.len() < 8 {
+                break;
+            }
+            // TTY services are not multiplexed; pass their output through as is.
+            if self.buffer[0] > 2 || self.buffer[1..4] != [0, 0, 0] {
+                outputs.push(ContainerOutput {
+                    stream: "stdout".to_string(),
+                    message: String::from_utf8_lossy(&self.buffer).into_owned(),
+                });
+                self.buffer.clear();
+                break;
+            }
+
+            let length = u32::from_be_bytes([
+                self.buffer[4],
+                self.buffer[5],
+                self.buffer[6],
+                self.buffer[7],
+            ]) as usize;
+            if self.buffer.len() < 8 + length {
+                break;
+            }
+
+            let stream = if self.buffer[0] == 2 {
+                "stderr"
+            } else {
+                "stdout"
+            };
+            let frame: Vec<u8> = self.buffer.drain(..8 + length).skip(8).collect();
+            outputs.push(ContainerOutput {
+                stream: stream.to_string(),
+                message: String::from_utf8_lossy(&frame).into_owned(),
+            });
+        }
+
+        outputs
+    }
+
+    /// Returns whatever is left once the stream has ended: short TTY output
+    /// that never filled a header, or the tail of a truncated frame.
+    fn flush(&mut self) -> Option<ContainerOutput> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let message = String::from_utf8_lossy(&self.buffer).into_owned();
+        self.buffer.clear();
+        Some(ContainerOutput {
+            stream: "stdout".to_string(),
+            message,
+        })
+    }
+}
+
+/// Parses `published:target[/protocol]`, e.g. `8080:80` or `5353:53/udp`.
+fn parse_service_port(mapping: &str) -> Result<EndpointPortConfig, CommandError> {
+    let invalid = || {
+        CommandError::UnexpectedError(format!(
+            "Invalid port mapping '{}': expected published:target[/protocol]",
+            mapping
+        ))
+    };
+
+    let (ports, protocol) = match mapping.split_once('/') {
+        Some((ports, protocol)) => (ports, protocol),
+        None => (mapping, "tcp"),
+    };
+    let (published, target) = ports.split_once(':').ok_or_else(invalid)?;
+    let protocol = match protocol {
+        "tcp" => EndpointPortConfigProtocolEnum::TCP,
+        "udp" => EndpointPortConfigProtocolEnum::UDP,
+        "sctp" => EndpointPortConfigProtocolEnum::SCTP,
+        _ => return Err(invalid()),
+    };
+
+    Ok(EndpointPortConfig {
+        protocol: Some(protocol),
+        published_port: Some(published.parse::<u16>().map_err(|_| invalid())?.into()),
+        target_port: Some(target.parse::<u16>().map_err(|_| invalid())?.into()),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(stream: u8, payload: &str) -> Vec<u8> {
+        let mut frame = vec![stream, 0, 0, 0];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload.as_bytes());
+        frame
+    }
+
+    fn messages(outputs: Vec<ContainerOutput>) -> Vec<(String, String)> {
+        outputs
+            .into_iter()
+            .map(|output| (output.stream, output.message))
+            .collect()
+    }
+
+    #[test]
+    fn demuxes_frames() {
+        let mut data = frame(1, "hello\n");
+        data.extend(frame(2, "oops\n"));
+
+        assert_eq!(
+            messages(LogDemuxer::default().push(&data)),
+            [
+                ("stdout".to_string(), "hello\n".to_string()),
+                ("stderr".to_string(), "oops\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn buffers_frames_split_across_chunks() {
+        let data = frame(1, "split message");
+        let mut demuxer = LogDemuxer::default();
+
+        assert!(demuxer.push(&data[..5]).is_empty());
+        assert!(demuxer.push(&data[5..12]).is_empty());
+        assert_eq!(
+            messages(demuxer.push(&data[12..])),
+            [("stdout".to_string(), "split message".to_string())]
+        );
+        assert!(demuxer.buffer.is_empty());
+    }
+
+    #[test]
+    fn passes_tty_output_through() {
+        assert_eq!(
+            messages(LogDemuxer::default().push(b"plain tty output\n")),
+            [("stdout".to_string(), "plain tty output\n".to_string())]
+        );
+    }
+
+    #[test]
+    fn flushes_short_tty_output() {
+        let mut demuxer = LogDemuxer::default();
+
+        assert!(demuxer.push(b"ok\n").is_empty());
+        assert_eq!(
+            messages(demuxer.flush().into_iter().collect()),
+            [("stdout".to_string(), "ok\n".to_string())]
+        );
+        assert!(demuxer.flush().is_none());
+    }
+
+    #[test]
+    fn flushes_trailing_partial_frame() {
+        let mut data = frame(1, "done\n");
+        data.extend_from_slice(b"tail");
+        let mut demuxer = LogDemuxer::default();
+
+        assert_eq!(
+            messages(demuxer.push(&data)),
+            [("stdout".to_string(), "done\n".to_string())]
+        );
+        assert_eq!(
+            messages(demuxer.flush().into_iter().collect()),
+            [("stdout".to_string(), "tail".to_string())]
+        );
+    }
+
+    #[test]
+    fn parses_service_ports() {
+        let port = parse_service_port("8080:80").unwrap();
+        assert_eq!(port.published_port, Some(8080));
+        assert_eq!(port.target_port, Some(80));
+        assert_eq!(port.protocol, Some(EndpointPortConfigProtocolEnum::TCP));
+
+        let port = parse_service_port("5353:53/udp").unwrap();
+        assert_eq!(port.protocol, Some(EndpointPortConfigProtocolEnum::UDP));
+    }
+
+    #[test]
+    fn rejects_invalid_service_ports() {
+        for mapping in ["80", "a:80", "8080:80/icmp", "70000:80", "-1:80", "8080:"] {
+            assert!(parse_service_port(mapping).is_err(), "{}", mapping);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use bollard::{ClientVersion, Docker};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::error::CommandError;
+use crate::payload::{ApiVersionWarning, EngineComponent, EngineInfo, EngineVersion};
+use crate::AppState;
+
+/// Oldest Engine API the app is written against: 1.41 (Docker 20.10) is the
+/// first to report the cgroup version and to accept `platform` everywhere we
+/// pass it.
+const REQUIRED_API_VERSION: ClientVersion = ClientVersion {
+    major_version: 1,
+    minor_version: 41,
+};
+
+#[tauri::command]
+pub async fn system_info(state: State<'_, AppState>) -> Result<EngineInfo, CommandError> {
+    let info = state
+        .docker
+        .info()
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to read engine info: {}", e)))?;
+
+    let security_options = info.security_options.unwrap_or_default();
+    let registry = info.registry_config.unwrap_or_default();
+
+    Ok(EngineInfo {
+        name: info.name,
+        server_version: info.server_version,
+        operating_system: info.operating_system,
+        os_type: info.os_type,
+        architecture: info.architecture,
+        kernel_version: info.kernel_version,
+        storage_driver: info.driver,
+        cgroup_driver: info.cgroup_driver.map(|driver| driver.to_string()),
+        cgroup_version: info.cgroup_version.map(|version| version.to_string()),
+        rootless: security_options
+            .iter()
+            .any(|option| option == "name=rootless"),
+        security_options,
+        cpus: info.ncpu,
+        memory_total: info.mem_total,
+        index_server_address: info.index_server_address,
+        registry_mirrors: registry.mirrors.unwrap_or_default(),
+        insecure_registries: registry.insecure_registry_cidrs.unwrap_or_default(),
+        warnings: info.warnings.unwrap_or_default(),
+    })
+}
+
+#[tauri::command]
+pub async fn system_version(state: State<'_, AppState>) -> Result<EngineVersion, CommandError> {
+    let docker = &state.docker;
+    let version = docker
+        .version()
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to read engine version: {}", e)))?;
+    let negotiated = docker.client_version();
+
+    Ok(EngineVersion {
+        version: version.version,
+        api_version: version.api_version,
+        min_api_version: version.min_api_version,
+        negotiated_api_version: negotiated.to_string(),
+        required_api_version: REQUIRED_API_VERSION.to_string(),
+        api_supported: negotiated >= REQUIRED_API_VERSION,
+        os: version.os,
+        arch: version.arch,
+        kernel_version: version.kernel_version,
+        go_version: version.go_version,
+        git_commit: version.git_commit,
+        build_time: version.build_time,
+        components: version
+            .components
+            .unwrap_or_default()
+            .into_iter()
+            .map(|component| EngineComponent {
+                name: component.name,
+                version: component.version,
+            })
+            .collect(),
+    })
+}
+
+/// Downgrades the shared client to the daemon's API version (bollard defaults
+/// to its newest) and emits `engine-api-outdated` when that falls below what
+/// the app needs. The version lives behind an `Arc`, so negotiating a clone
+/// updates the client held in `AppState` as well.
+pub(crate) async fn negotiate_api_version(app_handle: AppHandle, docker: Docker) {
+    let Ok(docker) = docker.negotiate_version().await else {
+        return;
+    };
+
+    let negotiated = docker.client_version();
+    if negotiated >= REQUIRED_API_VERSION {
+        return;
+    }
+
+    let warning = ApiVersionWarning {
+        negotiated_api_version: negotiated.to_string(),
+        required_api_version: REQUIRED_API_VERSION.to_string(),
+        message: format!(
+            "Docker API {} is older than {}; some features may fail",
+            negotiated, REQUIRED_API_VERSION
+        ),
+    };
+    let _ = app_handle.emit("engine-api-outdated", warning);
+}
@@ -0,0 +1,549 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use bollard::container::{Config, CreateContainerOptions};
+use bollard::secret::HostConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::payload::{ContainerTemplate, PullPolicy, TemplateImportResult, TemplateInfo};
+use crate::ports::{parse_port, port_config};
+use crate::settings::write_json;
+use crate::{ensure_image, AppState};
+
+pub const TEMPLATES_FILE: &str = "templates.json";
+const TEMPLATES_VERSION: u32 = 1;
+
+/// On-disk layout of both the template library and exported files.
+#[derive(Serialize, Deserialize)]
+struct TemplateFile {
+    version: u32,
+    templates: Vec<ContainerTemplate>,
+}
+
+/// Named container templates persisted in the app config dir.
+#[derive(Default)]
+pub struct TemplateStore {
+    path: Mutex<Option<PathBuf>>,
+    templates: Mutex<BTreeMap<String, ContainerTemplate>>,
+}
+
+impl TemplateStore {
+    /// Never fails: an unreadable or invalid library is logged and left
+    /// empty so it cannot keep the app from starting.
+    pub fn load(&self, path: PathBuf) {
+        let templates = match std::fs::read_to_string(&path) {
+            Ok(contents) => match read_templates(&contents) {
+                Ok(templates) => templates,
+                Err(e) => {
+                    println!("Ignoring template file '{}': {}", path.display(), e);
+                    let _ = std::fs::rename(&path, path.with_extension("json.bak"));
+                    Vec::new()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                println!("Failed to read templates from '{}': {}", path.display(), e);
+                Vec::new()
+            }
+        };
+
+        if let Ok(mut current) = self.lock_templates() {
+            *current = templates
+                .into_iter()
+                .map(|template| (template.name.clone(), template))
+                .collect();
+        }
+        if let Ok(mut current_path) = self.path.lock() {
+            *current_path = Some(path);
+        }
+    }
+
+    fn lock_templates(
+        &self,
+    ) -> Result<MutexGuard<'_, BTreeMap<String, ContainerTemplate>>, CommandError> {
+        self.templates
+            .lock()
+            .map_err(|e| CommandError::UnexpectedError(format!("Failed to read templates: {}", e)))
+    }
+
+    fn get(&self, name: &str) -> Result<ContainerTemplate, CommandError> {
+        self.lock_templates()?.get(name).cloned().ok_or_else(|| {
+            CommandError::UnexpectedError(format!("Template '{}' does not exist", name))
+        })
+    }
+
+    /// Applies `change` to the library and persists the result; the in-memory
+    /// copy is only replaced once the file has been written.
+    fn modify<T, F>(&self, change: F) -> Result<T, CommandError>
+    where
+        F: FnOnce(&mut BTreeMap<String, ContainerTemplate>) -> Result<T, CommandError>,
+    {
+        let path = self
+            .path
+            .lock()
+            .map_err(|e| CommandError::UnexpectedError(format!("Failed to save templates: {}", e)))?
+            .clone()
+            .ok_or_else(|| {
+                CommandError::UnexpectedError("Templates have not been loaded yet".to_string())
+            })?;
+
+        let mut templates = self.lock_templates()?;
+        let mut updated = templates.clone();
+        let result = change(&mut updated)?;
+
+        write_json(
+            &path,
+            &TemplateFile {
+                version: TEMPLATES_VERSION,
+                templates: updated.values().cloned().collect(),
+            },
+        )?;
+        *templates = updated;
+
+        Ok(result)
+    }
+}
+
+fn read_templates(contents: &str) -> Result<Vec<ContainerTemplate>, CommandError> {
+    let file: TemplateFile = serde_json::from_str(contents)
+        .map_err(|e| CommandError::UnexpectedError(format!("Invalid template file: {}", e)))?;
+
+    if file.version > TEMPLATES_VERSION {
+        return Err(CommandError::UnexpectedError(format!(
+            "Template file version {} is newer than supported version {}",
+            file.version, TEMPLATES_VERSION
+        )));
+    }
+    for template in &file.templates {
+        validate(template)?;
+    }
+
+    Ok(file.templates)
+}
+
+fn validate(template: &ContainerTemplate) -> Result<(), CommandError> {
+    if template.name.trim().is_empty() {
+        return Err(CommandError::UnexpectedError(
+            "Template name cannot be empty".to_string(),
+        ));
+    }
+    if template.image.trim().is_empty() {
+        return Err(CommandError::UnexpectedError(format!(
+            "Template '{}' has no image",
+            template.name
+        )));
+    }
+    // Entries with placeholders are checked once `render` has filled them in.
+    for port in template.ports.iter().filter_map(|port| literal(port)) {
+        parse_port(&port).map_err(|e| {
+            CommandError::UnexpectedError(format!("Template '{}': {}", template.name, e))
+        })?;
+    }
+    for env in template.env.iter().filter_map(|env| literal(env)) {
+        if !env.contains('=') {
+            return Err(CommandError::UnexpectedError(format!(
+                "Invalid environment entry '{}' in template '{}': expected KEY=VALUE",
+                env, template.name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `text` with `$$` unescaped, or `None` if it contains placeholders.
+fn literal(text: &str) -> Option<String> {
+    substitute(text, &mut |_| None).ok()
+}
+
+/// Collects the `${NAME}` placeholders used anywhere in the template.
+fn template_variables(template: &ContainerTemplate) -> Vec<String> {
+    let mut variables = BTreeSet::new();
+    if let Ok(value) = serde_json::to_value(template) {
+        visit_strings(&value, &mut |text| {
+            let _ = substitute(text, &mut |name| {
+                variables.insert(name.to_string());
+                Some(String::new())
+            });
+        });
+    }
+
+    variables.into_iter().collect()
+}
+
+fn visit_strings(value: &Value, visit: &mut impl FnMut(&str)) {
+    match value {
+        Value::String(text) => visit(text),
+        Value::Array(items) => items.iter().for_each(|item| visit_strings(item, visit)),
+        Value::Object(fields) => fields
+            .values()
+            .for_each(|field| visit_strings(field, visit)),
+        _ => {}
+    }
+}
+
+fn map_strings(
+    value: &mut Value,
+    map: &mut impl FnMut(&str) -> Result<String, CommandError>,
+) -> Result<(), CommandError> {
+    match value {
+        Value::String(text) => *text = map(text)?,
+        Value::Array(items) => {
+            for item in items {
+                map_strings(item, map)?;
+            }
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                map_strings(field, map)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Expands `${NAME}` using `lookup`; `$$` stands for a literal `$`. Returns
+/// the name of the first variable `lookup` cannot resolve.
+fn substitute(
+    text: &str,
+    lookup: &mut impl FnMut(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find('$') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if let Some(after) = rest.strip_prefix("$$") {
+            result.push('$');
+            rest = after;
+        } else if let Some((name, after)) = rest
+            .strip_prefix("${")
+            .and_then(|inner| inner.split_once('}'))
+            .filter(|(name, _)| is_variable_name(name))
+        {
+            result.push_str(&lookup(name).ok_or_else(|| name.to_string())?);
+            rest = after;
+        } else {
+            result.push('$');
+            rest = &rest[1..];
+        }
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn render(
+    template: &ContainerTemplate,
+    variables: &HashMap<String, String>,
+) -> Result<ContainerTemplate, CommandError> {
+    let missing: Vec<String> = template_variables(template)
+        .into_iter()
+        .filter(|name| !variables.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(CommandError::UnexpectedError(format!(
+            "Missing values for template variables: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let mut value = serde_json::to_value(template)
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to render template: {}", e)))?;
+    map_strings(&mut value, &mut |text| {
+        substitute(text, &mut |name| variables.get(name).cloned()).map_err(|name| {
+            CommandError::UnexpectedError(format!("Missing value for template variable '{}'", name))
+        })
+    })?;
+
+    serde_json::from_value(value)
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to render template: {}", e)))
+}
+
+fn template_config(template: ContainerTemplate) -> Result<Config<String>, CommandError> {
+    let (exposed_ports, port_bindings) = port_config(template.ports.iter().map(String::as_str))
+        .map_err(|e| {
+            CommandError::UnexpectedError(format!("Template '{}': {}", template.name, e))
+        })?;
+
+    Ok(Config {
+        image: Some(template.image),
+        cmd: template.command,
+        env: Some(template.env),
+        labels: Some(template.labels),
+        exposed_ports: Some(exposed_ports),
+        host_config: Some(HostConfig {
+            port_bindings: Some(port_bindings),
+            binds: Some(template.volumes),
+            network_mode: template.network,
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+#[tauri::command]
+pub async fn list_templates(state: State<'_, AppState>) -> Result<Vec<TemplateInfo>, CommandError> {
+    let templates = state.templates.lock_templates()?;
+
+    Ok(templates
+        .values()
+        .map(|template| TemplateInfo {
+            variables: template_variables(template),
+            template: template.clone(),
+        })
+        .collect())
+}
+
+/// Creates or edits a template. Pass `previous_name` to rename one.
+#[tauri::command]
+pub async fn save_template(
+    state: State<'_, AppState>,
+    template: ContainerTemplate,
+    previous_name: Option<String>,
+) -> Result<(), CommandError> {
+    validate(&template)?;
+
+    state.templates.modify(|templates| {
+        let renamed_from = previous_name.filter(|name| *name != template.name);
+        if let Some(previous_name) = &renamed_from {
+            if templates.remove(previous_name).is_none() {
+                return Err(CommandError::UnexpectedError(format!(
+                    "Template '{}' does not exist",
+                    previous_name
+                )));
+            }
+        }
+        if renamed_from.is_some() && templates.contains_key(&template.name) {
+            return Err(CommandError::UnexpectedError(format!(
+                "Template '{}' already exists",
+                template.name
+            )));
+        }
+
+        templates.insert(template.name.clone(), template);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn delete_template(
+    state: State<'_, AppState>,
+    template_name: &str,
+) -> Result<(), CommandError> {
+    state.templates.modify(|templates| {
+        templates.remove(template_name).map(|_| ()).ok_or_else(|| {
+            CommandError::UnexpectedError(format!("Template '{}' does not exist", template_name))
+        })
+    })
+}
+
+#[tauri::command]
+pub async fn instantiate_template(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    template_name: &str,
+    container_name: Option<String>,
+    variables: Option<HashMap<String, String>>,
+    pull_policy: Option<PullPolicy>,
+) -> Result<String, CommandError> {
+    let docker = &state.docker;
+    let template = render(
+        &state.templates.get(template_name)?,
+        &variables.unwrap_or_default(),
+    )?;
+    validate(&template)?;
+    let image = template.image.clone();
+    let config = template_config(template)?;
+
+    ensure_image(&app_handle, docker, &image, pull_policy.unwrap_or_default()).await?;
+
+    let options =
+        container_name
+            .filter(|name| !name.is_empty())
+            .map(|name| CreateContainerOptions {
+                name,
+                platform: None,
+            });
+    let response = docker
+        .create_container(options, config)
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to create container from template '{}': {}",
+                template_name, e
+            ))
+        })?;
+
+    docker
+        .start_container::<String>(&response.id, None)
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to start container: {}", e)))?;
+
+    Ok(response.id)
+}
+
+#[tauri::command]
+pub async fn export_templates(
+    state: State<'_, AppState>,
+    path: PathBuf,
+    template_names: Option<Vec<String>>,
+) -> Result<(), CommandError> {
+    let templates = match template_names {
+        Some(names) => names
+            .iter()
+            .map(|name| state.templates.get(name))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => state
+            .templates
+            .lock_templates()?
+            .values()
+            .cloned()
+            .collect(),
+    };
+
+    write_json(
+        &path,
+        &TemplateFile {
+            version: TEMPLATES_VERSION,
+            templates,
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn import_templates(
+    state: State<'_, AppState>,
+    path: PathBuf,
+    overwrite: Option<bool>,
+) -> Result<TemplateImportResult, CommandError> {
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        CommandError::UnexpectedError(format!("Failed to read '{}': {}", path.display(), e))
+    })?;
+    let incoming = read_templates(&contents)?;
+    let overwrite = overwrite.unwrap_or(false);
+
+    state.templates.modify(|templates| {
+        let mut result = TemplateImportResult {
+            imported: Vec::new(),
+            skipped: Vec::new(),
+        };
+        for template in incoming {
+            if !overwrite && templates.contains_key(&template.name) {
+                result.skipped.push(template.name);
+            } else {
+                result.imported.push(template.name.clone());
+                templates.insert(template.name.clone(), template);
+            }
+        }
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(ports: &[&str], env: &[&str]) -> ContainerTemplate {
+        ContainerTemplate {
+            name: "web".to_string(),
+            description: None,
+            image: "nginx:${TAG}".to_string(),
+            command: None,
+            ports: ports.iter().map(|port| port.to_string()).collect(),
+            env: env.iter().map(|env| env.to_string()).collect(),
+            volumes: Vec::new(),
+            network: None,
+            labels: HashMap::new(),
+        }
+    }
+
+    fn variables(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_variables_and_escapes() {
+        let values = variables(&[("HOST", "db"), ("PORT", "5432")]);
+        let mut lookup = |name: &str| values.get(name).cloned();
+
+        assert_eq!(
+            substitute("postgres://${HOST}:${PORT}/app", &mut lookup),
+            Ok("postgres://db:5432/app".to_string())
+        );
+        assert_eq!(
+            substitute("cost: $$5, ${HOST}", &mut lookup),
+            Ok("cost: $5, db".to_string())
+        );
+        assert_eq!(
+            substitute("$HOST ${1X} ${ unterminated $", &mut lookup),
+            Ok("$HOST ${1X} ${ unterminated $".to_string())
+        );
+        assert_eq!(
+            substitute("${HOST}-${MISSING}", &mut lookup),
+            Err("MISSING".to_string())
+        );
+    }
+
+    #[test]
+    fn lists_template_variables() {
+        let template = template(&["${HOST_PORT}:80"], &["MODE=${MODE}", "PRICE=$${TAG}"]);
+
+        assert_eq!(template_variables(&template), ["HOST_PORT", "MODE", "TAG"]);
+    }
+
+    #[test]
+    fn renders_every_string_field() {
+        let template = template(&["${HOST_PORT}:80"], &["MODE=${MODE}"]);
+        let rendered = render(
+            &template,
+            &variables(&[("HOST_PORT", "8080"), ("MODE", "prod"), ("TAG", "1.27")]),
+        )
+        .unwrap();
+
+        assert_eq!(rendered.image, "nginx:1.27");
+        assert_eq!(rendered.ports, ["8080:80"]);
+        assert_eq!(rendered.env, ["MODE=prod"]);
+    }
+
+    #[test]
+    fn render_reports_missing_variables() {
+        let template = template(&["${HOST_PORT}:80"], &[]);
+        let error = render(&template, &variables(&[("TAG", "1.27")])).unwrap_err();
+
+        assert!(error.to_string().contains("HOST_PORT"), "{}", error);
+    }
+
+    #[test]
+    fn validates_placeholders_only_after_rendering() {
+        let template = template(&["${HOST_PORT}:80"], &["${ENTRY}"]);
+        assert!(validate(&template).is_ok());
+
+        let rendered = render(
+            &template,
+            &variables(&[("HOST_PORT", "http"), ("ENTRY", "MODE=prod"), ("TAG", "1")]),
+        )
+        .unwrap();
+        assert!(validate(&rendered).is_err());
+        assert!(template_config(rendered).is_err());
+    }
+}
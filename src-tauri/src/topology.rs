@@ -0,0 +1,181 @@
+use bollard::container::ListContainersOptions;
+use bollard::Docker;
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::payload::{
+    NetworkTopology, TopologyEdge, TopologyFormat, TopologyNode, TopologyNodeKind,
+};
+use crate::{inspect_network_inner, list_networks_inner, AppState};
+
+const HOST_NODE_ID: &str = "host";
+
+#[tauri::command]
+pub async fn network_topology(state: State<'_, AppState>) -> Result<NetworkTopology, CommandError> {
+    build_topology(&state.docker).await
+}
+
+#[tauri::command]
+pub async fn export_network_topology(
+    state: State<'_, AppState>,
+    format: TopologyFormat,
+) -> Result<String, CommandError> {
+    let topology = build_topology(&state.docker).await?;
+
+    match format {
+        TopologyFormat::Json => serde_json::to_string_pretty(&topology).map_err(|e| {
+            CommandError::UnexpectedError(format!("Failed to serialize topology: {}", e))
+        }),
+        TopologyFormat::Dot => Ok(to_dot(&topology)),
+    }
+}
+
+async fn build_topology(docker: &Docker) -> Result<NetworkTopology, CommandError> {
+    let networks = list_networks_inner(docker).await?;
+    let details = futures_util::future::join_all(
+        networks
+            .iter()
+            .map(|network| inspect_network_inner(docker, &network.id)),
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let containers = docker
+        .list_containers(Some(ListContainersOptions::<String> {
+            all: true,
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to list containers: {}", e)))?;
+
+    let mut topology = NetworkTopology::default();
+    topology.nodes.push(TopologyNode {
+        id: HOST_NODE_ID.to_string(),
+        kind: TopologyNodeKind::Host,
+        label: "host".to_string(),
+        detail: None,
+    });
+
+    for network in details {
+        let network_node = format!("network:{}", network.id);
+        topology.nodes.push(TopologyNode {
+            id: network_node.clone(),
+            kind: TopologyNodeKind::Network,
+            label: network.name,
+            detail: network.driver,
+        });
+
+        for endpoint in network.containers {
+            topology.edges.push(TopologyEdge {
+                source: format!("container:{}", endpoint.container_id),
+                target: network_node.clone(),
+                ipv4_address: endpoint.ipv4_address,
+                ipv6_address: endpoint.ipv6_address,
+                aliases: endpoint.aliases,
+                ports: Vec::new(),
+            });
+        }
+    }
+
+    for container in containers {
+        let Some(id) = container.id else {
+            continue;
+        };
+        let container_node = format!("container:{}", id);
+        let name = container
+            .names
+            .and_then(|names| names.first().cloned())
+            .map(|name| name.trim_start_matches('/').to_string())
+            .unwrap_or_else(|| id.chars().take(12).collect());
+
+        topology.nodes.push(TopologyNode {
+            id: container_node.clone(),
+            kind: TopologyNodeKind::Container,
+            label: name,
+            detail: container.image,
+        });
+
+        let ports: Vec<String> = container
+            .ports
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|port| {
+                let public_port = port.public_port?;
+                let protocol = port
+                    .typ
+                    .map(|typ| typ.to_string())
+                    .unwrap_or_else(|| "tcp".to_string());
+                Some(format!(
+                    "{}:{}->{}/{}",
+                    port.ip.unwrap_or_else(|| "0.0.0.0".to_string()),
+                    public_port,
+                    port.private_port,
+                    protocol
+                ))
+            })
+            .collect();
+
+        if !ports.is_empty() {
+            topology.edges.push(TopologyEdge {
+                source: container_node,
+                target: HOST_NODE_ID.to_string(),
+                ipv4_address: None,
+                ipv6_address: None,
+                aliases: Vec::new(),
+                ports,
+            });
+        }
+    }
+
+    Ok(topology)
+}
+
+fn to_dot(topology: &NetworkTopology) -> String {
+    let mut dot = String::from("graph topology {\n    rankdir=LR;\n");
+
+    for node in &topology.nodes {
+        let shape = match node.kind {
+            TopologyNodeKind::Network => "ellipse",
+            TopologyNodeKind::Container => "box",
+            TopologyNodeKind::Host => "house",
+        };
+        let label = match &node.detail {
+            Some(detail) => format!("{}\n{}", node.label, detail),
+            None => node.label.clone(),
+        };
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{}\", shape={}];\n",
+            escape(&node.id),
+            escape(&label),
+            shape
+        ));
+    }
+
+    for edge in &topology.edges {
+        let label: Vec<String> = edge
+            .ipv4_address
+            .iter()
+            .chain(edge.ipv6_address.iter())
+            .cloned()
+            .chain((!edge.aliases.is_empty()).then(|| edge.aliases.join(", ")))
+            .chain(edge.ports.iter().cloned())
+            .collect();
+        dot.push_str(&format!(
+            "    \"{}\" -- \"{}\" [label=\"{}\"];\n",
+            escape(&edge.source),
+            escape(&edge.target),
+            escape(&label.join("\n"))
+        ));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use bollard::container::{InspectContainerOptions, ListContainersOptions};
+use bollard::Docker;
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::payload::{ContainerPatch, ContainerUpdateStatus};
+use crate::recreate::recreate_container_inner;
+use crate::reference::ImageReference;
+use crate::registry::registry_host;
+use crate::{pull_image_inner, AppState};
+
+/// Compares the image each container runs with what its tag points to in the
+/// registry. A container counts as outdated when the registry digest is not
+/// among the repo digests of its image, which also covers tags that were
+/// pulled again without recreating the container.
+#[tauri::command]
+pub async fn check_image_updates(
+    state: State<'_, AppState>,
+) -> Result<Vec<ContainerUpdateStatus>, CommandError> {
+    let docker = &state.docker;
+    let containers = docker
+        .list_containers(Some(ListContainersOptions {
+            filters: HashMap::from([("status", vec!["running"])]),
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| CommandError::DockerError(format!("Failed to list containers: {}", e)))?;
+
+    let mut remote_digests: HashMap<String, Result<String, String>> = HashMap::new();
+    let mut result = Vec::new();
+
+    for container in containers {
+        let container_id = container.id.unwrap_or_default();
+        let container_name = container.names.and_then(|names| {
+            names
+                .first()
+                .map(|name| name.trim_start_matches('/').to_string())
+        });
+        let image = configured_image(docker, &container_id, container.image).await;
+        let mut status = ContainerUpdateStatus {
+            container_id,
+            container_name,
+            image: image.clone(),
+            local_digest: None,
+            remote_digest: None,
+            outdated: false,
+            error: None,
+        };
+
+        let reference = match ImageReference::parse(&image) {
+            Ok(reference) if reference.digest.is_none() => reference,
+            Ok(_) => {
+                status.error = Some("Image is pinned to a digest".to_string());
+                result.push(status);
+                continue;
+            }
+            Err(e) => {
+                status.error = Some(e.to_string());
+                result.push(status);
+                continue;
+            }
+        };
+
+        let local_digests = match container.image_id {
+            Some(image_id) => repo_digests(docker, &image_id, &reference).await,
+            None => Vec::new(),
+        };
+        if local_digests.is_empty() {
+            status.error = Some("Image was not pulled from a registry".to_string());
+            result.push(status);
+            continue;
+        }
+
+        let key = reference.to_string();
+        if !remote_digests.contains_key(&key) {
+            let remote = state
+                .registry
+                .manifest_digest(&reference)
+                .await
+                .map_err(|e| e.to_string());
+            remote_digests.insert(key.clone(), remote);
+        }
+
+        match &remote_digests[&key] {
+            Ok(remote) => {
+                status.outdated = !local_digests.contains(remote);
+                status.remote_digest = Some(remote.clone());
+            }
+            Err(e) => status.error = Some(e.clone()),
+        }
+        status.local_digest = local_digests.into_iter().next();
+        result.push(status);
+    }
+
+    Ok(result)
+}
+
+/// Pulls the container's image tag again and recreates the container on it.
+#[tauri::command]
+pub async fn update_container(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    container_name: &str,
+) -> Result<String, CommandError> {
+    let docker = &state.docker;
+    let container = docker
+        .inspect_container(container_name, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| {
+            CommandError::DockerError(format!(
+                "Failed to inspect container '{}': {}",
+                container_name, e
+            ))
+        })?;
+    let image = container
+        .config
+        .and_then(|config| config.image)
+        .ok_or_else(|| {
+            CommandError::UnexpectedError(format!("Container '{}' has no image", container_name))
+        })?;
+
+    let reference = ImageReference::parse(&image)?;
+    pull_image_inner(&app_handle, docker, &reference, None, None).await?;
+
+    recreate_container_inner(
+        &app_handle,
+        &state,
+        container_name,
+        ContainerPatch::default(),
+    )
+    .await
+}
+
+/// The image name the container was created from. Once its tag moves to a
+/// newer image, the list endpoint reports the old image ID instead.
+async fn configured_image(docker: &Docker, container_id: &str, listed: Option<String>) -> String {
+    let listed = listed.unwrap_or_default();
+    if !listed.starts_with("sha256:") && !listed.chars().all(|c| c.is_ascii_hexdigit()) {
+        return listed;
+    }
+
+    docker
+        .inspect_container(container_id, None::<InspectContainerOptions>)
+        .await
+        .ok()
+        .and_then(|container| container.config)
+        .and_then(|config| config.image)
+        .unwrap_or(listed)
+}
+
+/// Digests recorded for `reference`'s repository on the given image.
+async fn repo_digests(docker: &Docker, image_id: &str, reference: &ImageReference) -> Vec<String> {
+    let Ok(image) = docker.inspect_image(image_id).await else {
+        return Vec::new();
+    };
+
+    image
+        .repo_digests
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry.split_once('@'))
+        .filter(|(name, _)| {
+            ImageReference::parse(name)
+                .map(|local| {
+                    registry_host(&local) == registry_host(reference)
+                        && local.repository.trim_start_matches("library/")
+                            == reference.repository.trim_start_matches("library/")
+                })
+                .unwrap_or(false)
+        })
+        .map(|(_, digest)| digest.to_string())
+        .collect()
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+use bollard::container::WaitContainerOptions;
+use bollard::errors::Error as BollardError;
+use bollard::models::ContainerWaitResponse;
+use bollard::system::EventsOptions;
+use bollard::Docker;
+use futures_util::StreamExt;
+use tauri::{Emitter, State};
+
+use crate::engine::{encode, parse_json, EngineClient};
+use crate::error::CommandError;
+use crate::payload::{ContainerExit, ContainerHealthEvent};
+use crate::AppState;
+
+const WAIT_CONDITIONS: [&str; 3] = ["not-running", "next-exit", "removed"];
+const HEALTH_MONITOR_RETRY: Duration = Duration::from_secs(5);
+
+/// Blocks until the container meets `condition` and reports how it exited.
+pub(crate) async fn wait_for_exit(
+    docker: &Docker,
+    container_name: &str,
+    condition: &str,
+) -> Result<ContainerExit, CommandError> {
+    if !WAIT_CONDITIONS.contains(&condition) {
+        return Err(CommandError::UnexpectedError(format!(
+            "Unknown wait condition '{}': expected one of {}",
+            condition,
+            WAIT_CONDITIONS.join(", ")
+        )));
+    }
+
+    let mut wait_stream = docker.wait_container(
+        container_name,
+        Some(WaitContainerOptions {
+            condition: condition.to_string(),
+        }),
+    );
+
+    let (status_code, error) = match wait_stream.next().await {
+        Some(Ok(response)) => (
+            response.status_code,
+            response.error.and_then(|error| error.message),
+        ),
+        Some(Err(BollardError::DockerContainerWaitError { error, code })) => {
+            (code, Some(error).filter(|error| !error.is_empty()))
+        }
+        Some(Err(e)) => {
+            return Err(CommandError::DockerError(format!(
+                "Failed to wait for container '{}': {}",
+                container_name, e
+            )))
+        }
+        None => {
+            return Err(CommandError::UnexpectedError(format!(
+                "Wait for container '{}' ended without a status",
+                container_name
+            )))
+        }
+    };
+
+    Ok(container_exit(docker, container_name, status_code, error).await)
+}
+
+/// Registers a wait through the engine client and only returns once the
+/// engine has accepted it, so unlike `wait_for_exit` a container started
+/// right after cannot exit (or be removed) before the wait is in place. The
+/// returned future resolves when the container meets `condition`.
+pub(crate) async fn register_wait(
+    docker: &Docker,
+    engine: &EngineClient,
+    container_name: &str,
+    condition: &str,
+) -> Result<impl Future<Output = Result<ContainerExit, CommandError>>, CommandError> {
+    if !WAIT_CONDITIONS.contains(&condition) {
+        return Err(CommandError::UnexpectedError(format!(
+            "Unknown wait condition '{}': expected one of {}",
+            condition,
+            WAIT_CONDITIONS.join(", ")
+        )));
+    }
+
+    let body = engine
+        .post_stream(&format!(
+            "/containers/{}/wait?condition={}",
+            encode(container_name),
+            condition
+        ))
+        .await?;
+
+    let docker = docker.clone();
+    let container_name = container_name.to_string();
+    Ok(async move {
+        let response: ContainerWaitResponse = parse_json(body).await?;
+        let error = response
+            .error
+            .and_then(|error| error.message)
+            .filter(|error| !error.is_empty());
+
+        Ok(container_exit(&docker, &container_name, response.status_code, error).await)
+    })
+}
+
+async fn container_exit(
+    docker: &Docker,
+    container_name: &str,
+    status_code: i64,
+    error: Option<String>,
+) -> ContainerExit {
+    // A removed container can no longer be inspected, so OOM state is best effort.
+    let oom_killed = docker
+        .inspect_container(container_name, None)
+        .await
+        .ok()
+        .and_then(|container| container.state)
+        .and_then(|state| state.oom_killed)
+        .unwrap_or(false);
+
+    ContainerExit {
+        container: container_name.to_string(),
+        status_code,
+        error,
+        oom_killed,
+    }
+}
+
+#[tauri::command]
+pub async fn wait_container(
+    state: State<'_, AppState>,
+    container_name: &str,
+    condition: Option<String>,
+) -> Result<ContainerExit, CommandError> {
+    let condition = condition.unwrap_or_else(|| "not-running".to_string());
+    wait_for_exit(&state.docker, container_name, &condition).await
+}
+
+/// Emits `container-exited` whenever the container stops with a non-zero code
+/// or is OOM-killed, until it is removed or unwatched.
+#[tauri::command]
+pub async fn watch_container(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    container_name: String,
+) -> Result<(), CommandError> {
+    let docker = state.docker.clone();
+    let name = container_name.clone();
+
+    let handle = tauri::async_runtime::spawn(async move {
+        while let Ok(exit) = wait_for_exit(&docker, &name, "next-exit").await {
+            if exit.status_code != 0 || exit.oom_killed {
+                let _ = app_handle.emit("container-exited", exit);
+            }
+        }
+    });
+
+    let mut watched = state
+        .watched_containers
+        .lock()
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to register watch: {}", e)))?;
+    // Watches end on their own once the container is removed; drop those.
+    watched.retain(|_, handle| !handle.inner().is_finished());
+    if let Some(previous) = watched.insert(container_name, handle) {
+        previous.abort();
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unwatch_container(
+    state: State<'_, AppState>,
+    container_name: &str,
+) -> Result<(), CommandError> {
+    let mut watched = state
+        .watched_containers
+        .lock()
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to unregister watch: {}", e)))?;
+    if let Some(handle) = watched.remove(container_name) {
+        handle.abort();
+    }
+
+    Ok(())
+}
+
+/// Follows the daemon's `health_status` events for the lifetime of the app and
+/// emits `container-unhealthy` whenever a container turns unhealthy.
+pub async fn monitor_health(app_handle: tauri::AppHandle, docker: Docker) {
+    let mut filters = HashMap::new();
+    filters.insert("type", vec!["container"]);
+    filters.insert("event", vec!["health_status"]);
+
+    loop {
+        let mut events = docker.events(Some(EventsOptions {
+            filters: filters.clone(),
+            ..Default::default()
+        }));
+
+        while let Some(Ok(event)) = events.next().await {
+            let status = event
+                .action
+                .as_deref()
+                .and_then(|action| action.strip_prefix("health_status: "));
+            if status != Some("unhealthy") {
+                continue;
+            }
+
+            let actor = event.actor.unwrap_or_default();
+            let _ = app_handle.emit(
+                "container-unhealthy",
+                ContainerHealthEvent {
+                    container_id: actor.id.unwrap_or_default(),
+                    container_name: actor
+                        .attributes
+                        .and_then(|attributes| attributes.get("name").cloned()),
+                    status: "unhealthy".to_string(),
+                },
+            );
+        }
+
+        // The event stream ends when the daemon restarts or goes away.
+        tokio::time::sleep(HEALTH_MONITOR_RETRY).await;
+    }
+}